@@ -8,15 +8,123 @@ pub(crate) use xitca_http::body::BodySize;
 
 use std::{
     fmt,
+    future::Future,
     marker::PhantomData,
-    pin::Pin,
+    pin::{pin, Pin},
     task::{Context, Poll},
 };
 
 use futures_core::stream::Stream;
+use futures_util::StreamExt;
+use xitca_http::body::{exact_body_hint, none_body_hint};
 
 use crate::bytes::Bytes;
 
+/// Type erased request body passed through [middleware](crate::middleware).
+pub enum RequestBody<'b> {
+    None,
+    Bytes(Bytes),
+    Stream(Pin<Box<dyn Stream<Item = Result<Bytes, BodyError>> + Send + 'b>>),
+}
+
+impl<'b> RequestBody<'b> {
+    /// Construct a new Stream variant of RequestBody.
+    pub fn stream<S, E>(stream: S) -> Self
+    where
+        S: Stream<Item = Result<Bytes, E>> + Send + 'b,
+        BodyError: From<E>,
+    {
+        Self::Stream(Box::pin(stream.map(|res| res.map_err(BodyError::from))))
+    }
+
+    // feed streaming body to middlewares through a channel so it's not required to be Send. the
+    // returned future transfers chunks of body and must be polled along with the request.
+    pub(crate) fn pipe<S, E>(stream: S) -> (Self, impl Future<Output = ()>)
+    where
+        S: Stream<Item = Result<Bytes, E>>,
+        BodyError: From<E>,
+    {
+        let (tx, rx) = tokio::sync::mpsc::channel(1);
+
+        let body = PipeBody {
+            rx,
+            hint: stream.size_hint(),
+        };
+
+        let feed = async move {
+            let mut stream = pin!(stream);
+            while let Some(res) = stream.next().await {
+                if tx.send(res.map_err(BodyError::from)).await.is_err() {
+                    return;
+                }
+            }
+        };
+
+        (Self::Stream(Box::pin(body)), feed)
+    }
+
+    /// Clone body when it's not a stream. Streaming body can only be sent once.
+    pub fn try_clone(&self) -> Option<Self> {
+        match *self {
+            Self::None => Some(Self::None),
+            Self::Bytes(ref bytes) => Some(Self::Bytes(bytes.clone())),
+            Self::Stream(_) => None,
+        }
+    }
+}
+
+impl Stream for RequestBody<'_> {
+    type Item = Result<Bytes, BodyError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        match this {
+            Self::None => Poll::Ready(None),
+            Self::Bytes(_) => match std::mem::replace(this, Self::None) {
+                Self::Bytes(bytes) => Poll::Ready(Some(Ok(bytes))),
+                _ => unreachable!(),
+            },
+            Self::Stream(stream) => stream.as_mut().poll_next(cx),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match *self {
+            Self::None => none_body_hint(),
+            Self::Bytes(ref bytes) => exact_body_hint(bytes.len()),
+            Self::Stream(ref stream) => stream.size_hint(),
+        }
+    }
+}
+
+// receiving end of RequestBody::pipe with size hint of the original stream.
+struct PipeBody {
+    rx: tokio::sync::mpsc::Receiver<Result<Bytes, BodyError>>,
+    hint: (usize, Option<usize>),
+}
+
+impl Stream for PipeBody {
+    type Item = Result<Bytes, BodyError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.get_mut().rx.poll_recv(cx)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.hint
+    }
+}
+
+impl fmt::Debug for RequestBody<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::None => write!(f, "RequestBody::None"),
+            Self::Bytes(ref bytes) => write!(f, "RequestBody::Bytes({bytes:?})"),
+            Self::Stream(_) => write!(f, "RequestBody::Stream(..)"),
+        }
+    }
+}
+
 #[allow(clippy::large_enum_variant)]
 pub enum ResponseBody<'c> {
     #[cfg(feature = "http1")]
@@ -25,6 +133,8 @@ pub enum ResponseBody<'c> {
     H2(crate::h2::body::ResponseBody),
     #[cfg(feature = "http3")]
     H3(crate::h3::body::ResponseBody),
+    #[cfg(any(feature = "compress-br", feature = "compress-gz", feature = "compress-de"))]
+    Decompress(Box<http_encoding::Coder<ResponseBody<'c>>>),
     // TODO: add http1 eof resposne body variant.
     #[allow(dead_code)]
     Eof(PhantomData<&'c ()>),
@@ -36,6 +146,11 @@ impl ResponseBody<'_> {
         if let Self::H1(ref mut body) = *self {
             body.conn().destroy_on_drop()
         }
+
+        #[cfg(any(feature = "compress-br", feature = "compress-gz", feature = "compress-de"))]
+        if let Self::Decompress(ref mut body) = *self {
+            body.get_mut().destroy_on_drop()
+        }
     }
 
     pub(crate) fn can_destroy_on_drop(&mut self) -> bool {
@@ -44,6 +159,11 @@ impl ResponseBody<'_> {
             return body.conn().is_destroy_on_drop();
         }
 
+        #[cfg(any(feature = "compress-br", feature = "compress-gz", feature = "compress-de"))]
+        if let Self::Decompress(ref mut body) = *self {
+            return body.get_mut().can_destroy_on_drop();
+        }
+
         false
     }
 }
@@ -57,6 +177,8 @@ impl fmt::Debug for ResponseBody<'_> {
             Self::H2(_) => write!(f, "ResponseBody::H2(..)"),
             #[cfg(feature = "http3")]
             Self::H3(_) => write!(f, "ResponseBody::H3(..)"),
+            #[cfg(any(feature = "compress-br", feature = "compress-gz", feature = "compress-de"))]
+            Self::Decompress(_) => write!(f, "ResponseBody::Decompress(..)"),
             Self::Eof(_) => write!(f, "ResponseBody::Eof"),
         }
     }
@@ -73,6 +195,11 @@ impl Stream for ResponseBody<'_> {
             Self::H2(body) => Pin::new(body).poll_next(_cx),
             #[cfg(feature = "http3")]
             Self::H3(body) => Pin::new(body).poll_next(_cx),
+            #[cfg(any(feature = "compress-br", feature = "compress-gz", feature = "compress-de"))]
+            Self::Decompress(body) => Pin::new(&mut **body).poll_next(_cx).map_err(|e| match e {
+                http_encoding::error::CoderError::Io(e) => BodyError::from(e),
+                http_encoding::error::CoderError::Stream(e) => e,
+            }),
             Self::Eof(_) => Poll::Ready(None),
         }
     }
@@ -1,12 +1,18 @@
 use std::{net::SocketAddr, time::Duration};
 
 use xitca_http::http::version::Version;
+use xitca_service::Service;
 
 use crate::{
     client::Client,
     date::DateTimeService,
+    error::Error,
+    middleware::{ServiceDyn, ServiceRequest},
     pool::Pool,
+    proxy::Proxy,
+    redirect::RedirectPolicy,
     resolver::{Resolve, Resolver},
+    response::Response,
     timeout::TimeoutConfig,
     tls::connector::{Connector, TlsConnect},
 };
@@ -18,6 +24,11 @@ pub struct ClientBuilder {
     timeout_config: TimeoutConfig,
     local_addr: Option<SocketAddr>,
     max_http_version: Version,
+    redirect_policy: RedirectPolicy,
+    proxies: Vec<Proxy>,
+    middlewares: Vec<Box<dyn ServiceDyn>>,
+    #[cfg(feature = "cookie")]
+    cookie_store: Option<std::sync::Arc<crate::cookie::CookieJar>>,
 }
 
 impl Default for ClientBuilder {
@@ -29,6 +40,11 @@ impl Default for ClientBuilder {
             timeout_config: TimeoutConfig::default(),
             local_addr: None,
             max_http_version: Version::HTTP_3,
+            redirect_policy: RedirectPolicy::default(),
+            proxies: Vec::new(),
+            middlewares: Vec::new(),
+            #[cfg(feature = "cookie")]
+            cookie_store: None,
         }
     }
 }
@@ -140,6 +156,54 @@ impl ClientBuilder {
         self
     }
 
+    /// Set policy of following redirect response.
+    ///
+    /// Default to follow at most 10 redirects. See [RedirectPolicy] for detail.
+    pub fn redirect_policy(mut self, policy: RedirectPolicy) -> Self {
+        self.redirect_policy = policy;
+        self
+    }
+
+    /// Add a proxy for outbound connections. Can be called multiple times and the first proxy
+    /// intercepting a request is used.
+    ///
+    /// Connections through proxy are not shared with direct connections in connection pool. See
+    /// [Proxy] for detail.
+    pub fn proxy(mut self, proxy: Proxy) -> Self {
+        self.proxies.push(proxy);
+        self
+    }
+
+    /// Add proxies from environment variables. See [Proxy::from_env] for detail.
+    pub fn proxy_from_env(mut self) -> Self {
+        self.proxies.extend(Proxy::from_env());
+        self
+    }
+
+    /// Add a middleware to client. Can be called multiple times and the first added middleware is
+    /// the outermost one receiving request.
+    ///
+    /// See [middleware](crate::middleware) for detail.
+    pub fn middleware<S>(mut self, middleware: S) -> Self
+    where
+        S: for<'c> Service<ServiceRequest<'c>, Response = Response<'c>, Error = Error> + Send + Sync + 'static,
+        for<'c> <S as Service<ServiceRequest<'c>>>::Future<'c>: Send,
+    {
+        self.middlewares.push(Box::new(middleware));
+        self
+    }
+
+    #[cfg(feature = "cookie")]
+    /// Set cookie store of client.
+    ///
+    /// Cookies from `Set-Cookie` header of responses are stored in the jar and matching cookies are
+    /// sent with following requests. The jar can be kept by caller to inspect, seed and persist
+    /// cookies. See [CookieJar](crate::cookie::CookieJar) for detail.
+    pub fn cookie_store(mut self, jar: std::sync::Arc<crate::cookie::CookieJar>) -> Self {
+        self.cookie_store = Some(jar);
+        self
+    }
+
     /// Finish the builder and construct [Client] instance.
     pub fn finish(self) -> Client {
         let mut client = {
@@ -234,6 +298,11 @@ impl ClientBuilder {
                     max_http_version: self.max_http_version,
                     local_addr: self.local_addr,
                     date_service: DateTimeService::new(),
+                    redirect_policy: self.redirect_policy,
+                    proxies: self.proxies,
+                    middlewares: self.middlewares,
+                    #[cfg(feature = "cookie")]
+                    cookie_store: self.cookie_store,
                     h3_client,
                 }
             }
@@ -247,6 +316,11 @@ impl ClientBuilder {
                 max_http_version: self.max_http_version,
                 local_addr: self.local_addr,
                 date_service: DateTimeService::new(),
+                redirect_policy: self.redirect_policy,
+                proxies: self.proxies,
+                middlewares: self.middlewares,
+                #[cfg(feature = "cookie")]
+                cookie_store: self.cookie_store,
             }
         };
 
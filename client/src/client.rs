@@ -15,7 +15,10 @@ use crate::{
     date::DateTimeService,
     error::{Error, TimeoutError},
     http::{self, uri, Method, Version},
+    middleware::ServiceDyn,
     pool::Pool,
+    proxy::{self, Proxy, ProxyKind, Target},
+    redirect::RedirectPolicy,
     request::Request,
     resolver::Resolver,
     timeout::{Timeout, TimeoutConfig},
@@ -31,6 +34,11 @@ pub struct Client {
     pub(crate) max_http_version: Version,
     pub(crate) local_addr: Option<SocketAddr>,
     pub(crate) date_service: DateTimeService,
+    pub(crate) redirect_policy: RedirectPolicy,
+    pub(crate) proxies: Vec<Proxy>,
+    pub(crate) middlewares: Vec<Box<dyn ServiceDyn>>,
+    #[cfg(feature = "cookie")]
+    pub(crate) cookie_store: Option<std::sync::Arc<crate::cookie::CookieJar>>,
     #[cfg(feature = "http3")]
     pub(crate) h3_client: h3_quinn::quinn::Endpoint,
 }
@@ -161,6 +169,13 @@ impl Client {
         self._ws(url, Version::HTTP_2)
     }
 
+    #[cfg(feature = "cookie")]
+    /// Get a reference of cookie store of client. Returns None when client is constructed without
+    /// cookie store.
+    pub fn cookie_store(&self) -> Option<&crate::cookie::CookieJar> {
+        self.cookie_store.as_deref()
+    }
+
     #[cfg(feature = "websocket")]
     fn _ws(&self, url: &str, version: Version) -> Result<crate::ws::WsRequest<'_, NoneBody<Bytes>>, Error> {
         let req = http_ws::client_request_from_uri(url, version)?.map(|_| Default::default());
@@ -170,12 +185,22 @@ impl Client {
 }
 
 impl Client {
+    // first proxy intercepting request to given uri.
+    pub(crate) fn proxy_for(&self, uri: &Uri<'_>) -> Option<&Proxy> {
+        self.proxies.iter().find(|proxy| proxy.intercept(uri))
+    }
+
     pub(crate) async fn make_connection(
         &self,
         connect: &mut Connect<'_>,
         timer: &mut Pin<Box<Sleep>>,
         max_version: Version,
+        proxy: Option<&Proxy>,
     ) -> Result<Connection, Error> {
+        if let Some(proxy) = proxy {
+            return self.make_proxy(connect, timer, max_version, proxy).await;
+        }
+
         match connect.uri {
             Uri::Tcp(_) => {
                 self.resolver
@@ -266,6 +291,72 @@ impl Client {
         }
     }
 
+    async fn make_proxy(
+        &self,
+        connect: &mut Connect<'_>,
+        timer: &mut Pin<Box<Sleep>>,
+        max_version: Version,
+        proxy: &Proxy,
+    ) -> Result<Connection, Error> {
+        // socks5 proxy without remote dns expects resolved address of target.
+        let target = match proxy.kind() {
+            ProxyKind::Socks5 { remote_dns: false } => {
+                self.resolver
+                    .resolve(connect)
+                    .timeout(timer.as_mut())
+                    .await
+                    .map_err(|_| TimeoutError::Resolve)??;
+
+                let addr = connect.addrs().next().ok_or(Error::Resolve)?;
+                Target::Addr(addr.ip())
+            }
+            _ => Target::Domain(connect.hostname()),
+        };
+
+        let mut proxy_connect = Connect::new(Uri::Tcp(proxy.uri()));
+
+        timer
+            .as_mut()
+            .reset(Instant::now() + self.timeout_config.resolve_timeout);
+
+        self.resolver
+            .resolve(&mut proxy_connect)
+            .timeout(timer.as_mut())
+            .await
+            .map_err(|_| TimeoutError::Resolve)??;
+
+        let mut stream = self.make_tcp(&mut proxy_connect, timer).await?;
+
+        if proxy.is_forward(&connect.uri) {
+            return Ok(stream.into());
+        }
+
+        timer
+            .as_mut()
+            .reset(Instant::now() + self.timeout_config.connect_timeout);
+
+        let port = connect.port();
+
+        match proxy.kind() {
+            ProxyKind::Http => {
+                proxy::tunnel(&mut stream, connect.hostname(), port, proxy.authorization())
+                    .timeout(timer.as_mut())
+                    .await
+            }
+            ProxyKind::Socks5 { .. } => {
+                proxy::socks5(&mut stream, proxy, target, port)
+                    .timeout(timer.as_mut())
+                    .await
+            }
+        }
+        .map_err(|_| TimeoutError::Connect)??;
+
+        match connect.uri {
+            Uri::Tls(_) => self.tls_handshake(stream, connect, timer, max_version).await,
+            _ => Ok(stream.into()),
+        }
+    }
+
     async fn make_tls(
         &self,
         connect: &mut Connect<'_>,
@@ -273,7 +364,16 @@ impl Client {
         max_version: Version,
     ) -> Result<Connection, Error> {
         let stream = self.make_tcp(connect, timer).await?;
+        self.tls_handshake(stream, connect, timer, max_version).await
+    }
 
+    async fn tls_handshake(
+        &self,
+        stream: TcpStream,
+        connect: &mut Connect<'_>,
+        timer: &mut Pin<Box<Sleep>>,
+        max_version: Version,
+    ) -> Result<Connection, Error> {
         timer
             .as_mut()
             .reset(Instant::now() + self.timeout_config.tls_connect_timeout);
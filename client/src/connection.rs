@@ -15,7 +15,7 @@ use xitca_http::http::uri::{Authority, PathAndQuery};
 #[cfg(unix)]
 use tokio::net::UnixStream;
 
-use crate::{tls::stream::TlsStream, uri::Uri};
+use crate::{proxy::Proxy, tls::stream::TlsStream, uri::Uri};
 
 #[cfg(feature = "http1")]
 /// A convince type alias for typing connection without interacting with pool.
@@ -160,6 +160,19 @@ pub enum ConnectionKey {
     Regular(Authority),
     #[cfg(unix)]
     Unix(AuthorityWithPath),
+    /// connection to target through proxy. proxy authority comes first.
+    Proxy(Authority, Authority),
+}
+
+impl ConnectionKey {
+    pub(crate) fn new(uri: &Uri<'_>, proxy: Option<&Proxy>) -> Self {
+        match (proxy, uri) {
+            (Some(proxy), Uri::Tcp(uri) | Uri::Tls(uri)) => {
+                ConnectionKey::Proxy(proxy.authority().clone(), uri.authority().unwrap().clone())
+            }
+            _ => ConnectionKey::from(uri),
+        }
+    }
 }
 
 #[doc(hidden)]
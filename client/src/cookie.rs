@@ -0,0 +1,511 @@
+//! cookie store of client.
+
+use std::{
+    fmt,
+    io::{self, BufRead, Write},
+    net::IpAddr,
+    sync::Mutex,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crate::http::{
+    header::{HeaderMap, HeaderValue, COOKIE, SET_COOKIE},
+    Uri,
+};
+
+/// A cookie store recording `Set-Cookie` headers from responses and providing `Cookie` header for
+/// requests.
+///
+/// Cookies are matched against request url by their domain, path, expiry time and secure attribute.
+///
+/// # Examples:
+/// ```rust
+/// use std::sync::Arc;
+///
+/// use xitca_client::{cookie::CookieJar, http::Uri, Client};
+///
+/// let jar = Arc::new(CookieJar::new());
+///
+/// // seed the jar like the cookie is set by server.
+/// let url = Uri::from_static("https://example.com/");
+/// assert!(jar.insert(&url, "session=abc; Path=/; Secure; HttpOnly"));
+///
+/// // cookies can be inspected through the jar.
+/// let cookies = jar.cookies_for(&url);
+/// assert_eq!(cookies[0].name(), "session");
+///
+/// # fn resolve(jar: Arc<CookieJar>) {
+/// let client = Client::builder().cookie_store(jar).finish();
+/// # }
+/// ```
+#[derive(Default)]
+pub struct CookieJar {
+    cookies: Mutex<Vec<Cookie>>,
+}
+
+/// A cookie stored in [CookieJar].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Cookie {
+    name: String,
+    value: String,
+    domain: String,
+    path: String,
+    expires: Option<SystemTime>,
+    secure: bool,
+    http_only: bool,
+    host_only: bool,
+}
+
+impl Cookie {
+    /// Name of cookie.
+    #[inline]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Value of cookie.
+    #[inline]
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    /// Domain cookie is sent to. Sub domains are included unless [Cookie::host_only] is true.
+    #[inline]
+    pub fn domain(&self) -> &str {
+        &self.domain
+    }
+
+    /// Path prefix cookie is sent to.
+    #[inline]
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Expiry time of cookie. None for session cookie.
+    #[inline]
+    pub fn expires(&self) -> Option<SystemTime> {
+        self.expires
+    }
+
+    /// Cookie is only sent over secure connection.
+    #[inline]
+    pub fn secure(&self) -> bool {
+        self.secure
+    }
+
+    /// Cookie is not meant to be accessed from script.
+    #[inline]
+    pub fn http_only(&self) -> bool {
+        self.http_only
+    }
+
+    /// Cookie is only sent to the exact domain that set it.
+    #[inline]
+    pub fn host_only(&self) -> bool {
+        self.host_only
+    }
+
+    fn is_expired(&self, now: SystemTime) -> bool {
+        self.expires.map_or(false, |expires| expires <= now)
+    }
+
+    fn is_same(&self, other: &Self) -> bool {
+        self.name == other.name && self.domain == other.domain && self.path == other.path
+    }
+
+    fn matches(&self, url: &Uri, now: SystemTime) -> bool {
+        let Some(host) = url.host() else { return false };
+        let host = host.to_ascii_lowercase();
+
+        let domain_match = if self.host_only {
+            host == self.domain
+        } else {
+            domain_match(&host, &self.domain)
+        };
+
+        let secure = matches!(url.scheme_str(), Some("https" | "wss"));
+
+        domain_match && path_match(url.path(), &self.path) && (secure || !self.secure) && !self.is_expired(now)
+    }
+
+    // parse Set-Cookie header value received from given url.
+    fn parse(url: &Uri, set_cookie: &str, now: SystemTime) -> Option<Self> {
+        let host = url.host()?.to_ascii_lowercase();
+
+        let mut attrs = set_cookie.split(';');
+
+        let (name, value) = attrs.next()?.split_once('=')?;
+        let (name, value) = (name.trim(), value.trim());
+        if name.is_empty() {
+            return None;
+        }
+
+        let mut cookie = Cookie {
+            name: name.into(),
+            value: value.into(),
+            domain: host,
+            path: default_path(url.path()).into(),
+            expires: None,
+            secure: false,
+            http_only: false,
+            host_only: true,
+        };
+
+        let mut max_age = None;
+
+        for attr in attrs {
+            let (key, value) = attr.split_once('=').unwrap_or((attr, ""));
+            let (key, value) = (key.trim(), value.trim());
+
+            if key.eq_ignore_ascii_case("expires") {
+                if let Ok(expires) = httpdate::parse_http_date(value) {
+                    cookie.expires = Some(expires);
+                }
+            } else if key.eq_ignore_ascii_case("max-age") {
+                if let Ok(secs) = value.parse::<i64>() {
+                    max_age = Some(secs);
+                }
+            } else if key.eq_ignore_ascii_case("domain") {
+                let domain = value.strip_prefix('.').unwrap_or(value).to_ascii_lowercase();
+                if !domain.is_empty() {
+                    // domain must cover the host and can not be a top level domain or ip address.
+                    if !domain_match(&cookie.domain, &domain) || (!domain.contains('.') && domain != cookie.domain) {
+                        return None;
+                    }
+                    cookie.host_only = domain == cookie.domain && is_ip(&domain);
+                    cookie.domain = domain;
+                }
+            } else if key.eq_ignore_ascii_case("path") {
+                if value.starts_with('/') {
+                    cookie.path = value.into();
+                }
+            } else if key.eq_ignore_ascii_case("secure") {
+                cookie.secure = true;
+            } else if key.eq_ignore_ascii_case("httponly") {
+                cookie.http_only = true;
+            }
+        }
+
+        // Max-Age takes precedence over Expires.
+        if let Some(secs) = max_age {
+            // expiry beyond the range of SystemTime is treated as no expiry.
+            cookie.expires = match u64::try_from(secs) {
+                Ok(secs) if secs > 0 => now.checked_add(Duration::from_secs(secs)),
+                _ => Some(UNIX_EPOCH),
+            };
+        }
+
+        Some(cookie)
+    }
+}
+
+/// Display cookie in `name=value` form.
+impl fmt::Display for Cookie {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}={}", self.name, self.value)
+    }
+}
+
+impl CookieJar {
+    /// Construct an empty cookie jar.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Store cookie from `Set-Cookie` header value as if it's received from given url.
+    ///
+    /// Returns false when the value is malformed or not allowed to be set from given url. A
+    /// cookie with past expiry time removes the stored cookie of the same name, domain and path.
+    pub fn insert(&self, url: &Uri, set_cookie: &str) -> bool {
+        let now = SystemTime::now();
+        match Cookie::parse(url, set_cookie, now) {
+            Some(cookie) => {
+                self.store(cookie, now);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Remove cookie with given name, domain and path. Returns removed cookie.
+    pub fn remove(&self, name: &str, domain: &str, path: &str) -> Option<Cookie> {
+        let mut cookies = self.cookies.lock().unwrap();
+        let i = cookies
+            .iter()
+            .position(|c| c.name == name && c.domain.eq_ignore_ascii_case(domain) && c.path == path)?;
+        Some(cookies.remove(i))
+    }
+
+    /// Remove all cookies.
+    pub fn clear(&self) {
+        self.cookies.lock().unwrap().clear();
+    }
+
+    /// All cookies that are not expired.
+    pub fn cookies(&self) -> Vec<Cookie> {
+        let now = SystemTime::now();
+        let mut cookies = self.cookies.lock().unwrap();
+        cookies.retain(|c| !c.is_expired(now));
+        cookies.clone()
+    }
+
+    /// Cookies that would be sent with request to given url. Cookies with longer path come first.
+    pub fn cookies_for(&self, url: &Uri) -> Vec<Cookie> {
+        let now = SystemTime::now();
+        let mut cookies = self
+            .cookies
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|c| c.matches(url, now))
+            .cloned()
+            .collect::<Vec<_>>();
+        // sort is stable so cookies with the same path keep their creation order.
+        cookies.sort_by_key(|c| core::cmp::Reverse(c.path.len()));
+        cookies
+    }
+
+    /// Write cookies to given writer in Netscape cookie file format.
+    ///
+    /// Session cookie is written with expiry time of 0.
+    pub fn save<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writeln!(writer, "# Netscape HTTP Cookie File")?;
+
+        for c in self.cookies() {
+            let expires = c
+                .expires
+                .and_then(|e| e.duration_since(UNIX_EPOCH).ok())
+                .map(|d| d.as_secs())
+                .unwrap_or(0);
+
+            writeln!(
+                writer,
+                "{}{}\t{}\t{}\t{}\t{}\t{}\t{}",
+                if c.http_only { "#HttpOnly_" } else { "" },
+                c.domain,
+                bool_str(!c.host_only),
+                c.path,
+                bool_str(c.secure),
+                expires,
+                c.name,
+                c.value
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Read cookies from given reader in Netscape cookie file format and add them to the jar.
+    /// Expired cookies are ignored.
+    pub fn load<R: BufRead>(&self, reader: R) -> io::Result<()> {
+        let now = SystemTime::now();
+
+        for line in reader.lines() {
+            let line = line?;
+
+            let (line, http_only) = match line.strip_prefix("#HttpOnly_") {
+                Some(line) => (line, true),
+                None if line.starts_with('#') || line.trim().is_empty() => continue,
+                None => (line.as_str(), false),
+            };
+
+            let invalid = || io::Error::new(io::ErrorKind::InvalidData, format!("invalid cookie line: {line}"));
+
+            let mut fields = line.split('\t');
+            let mut next = || fields.next().ok_or_else(invalid);
+
+            let domain = next()?;
+            let include_sub = next()?;
+            let path = next()?;
+            let secure = next()?;
+            let expires = next()?.parse::<u64>().map_err(|_| invalid())?;
+            let name = next()?;
+            let value = next()?;
+
+            let cookie = Cookie {
+                name: name.into(),
+                value: value.into(),
+                domain: domain.strip_prefix('.').unwrap_or(domain).to_ascii_lowercase(),
+                path: path.into(),
+                expires: match expires {
+                    0 => None,
+                    secs => UNIX_EPOCH.checked_add(Duration::from_secs(secs)),
+                },
+                secure: secure.eq_ignore_ascii_case("TRUE"),
+                http_only,
+                host_only: !include_sub.eq_ignore_ascii_case("TRUE"),
+            };
+
+            self.store(cookie, now);
+        }
+
+        Ok(())
+    }
+
+    // record Set-Cookie headers of response received from given url.
+    pub(crate) fn store_response(&self, url: &Uri, headers: &HeaderMap) {
+        let now = SystemTime::now();
+        for value in headers.get_all(SET_COOKIE) {
+            if let Some(cookie) = value.to_str().ok().and_then(|v| Cookie::parse(url, v, now)) {
+                self.store(cookie, now);
+            }
+        }
+    }
+
+    // add matching cookies to request headers. cookies already in headers are kept in front.
+    pub(crate) fn attach_request(&self, url: &Uri, headers: &mut HeaderMap) {
+        let cookies = self.cookies_for(url);
+        if cookies.is_empty() {
+            return;
+        }
+
+        let mut value = headers
+            .get(COOKIE)
+            .and_then(|v| v.to_str().ok())
+            .map(String::from)
+            .unwrap_or_default();
+
+        for cookie in cookies {
+            if !value.is_empty() {
+                value.push_str("; ");
+            }
+            value.push_str(&cookie.to_string());
+        }
+
+        if let Ok(value) = HeaderValue::try_from(value) {
+            headers.insert(COOKIE, value);
+        }
+    }
+
+    fn store(&self, cookie: Cookie, now: SystemTime) {
+        let mut cookies = self.cookies.lock().unwrap();
+        let existing = cookies.iter().position(|c| c.is_same(&cookie));
+
+        match existing {
+            _ if cookie.is_expired(now) => {
+                if let Some(i) = existing {
+                    cookies.remove(i);
+                }
+            }
+            Some(i) => cookies[i] = cookie,
+            None => cookies.push(cookie),
+        }
+    }
+}
+
+impl fmt::Debug for CookieJar {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CookieJar").field("cookies", &self.cookies()).finish()
+    }
+}
+
+fn bool_str(b: bool) -> &'static str {
+    if b {
+        "TRUE"
+    } else {
+        "FALSE"
+    }
+}
+
+fn is_ip(host: &str) -> bool {
+    host.starts_with('[') || host.parse::<IpAddr>().is_ok()
+}
+
+// host is the domain or a sub domain of it. ip address only matches itself.
+fn domain_match(host: &str, domain: &str) -> bool {
+    host == domain
+        || (!is_ip(host)
+            && host
+                .strip_suffix(domain)
+                .map_or(false, |sub| sub.ends_with('.') && sub.len() > 1))
+}
+
+// request path is the cookie path or under it.
+fn path_match(path: &str, cookie_path: &str) -> bool {
+    let path = if path.is_empty() { "/" } else { path };
+    path.strip_prefix(cookie_path)
+        .map_or(false, |rest| rest.is_empty() || cookie_path.ends_with('/') || rest.starts_with('/'))
+}
+
+// directory of request path.
+fn default_path(path: &str) -> &str {
+    match path.rfind('/') {
+        Some(0) | None => "/",
+        Some(i) => &path[..i],
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn store_and_match() {
+        let jar = CookieJar::new();
+        let url = Uri::from_static("https://www.example.com/a/b");
+
+        assert!(jar.insert(&url, "a=1"));
+        assert!(jar.insert(&url, "b=2; Domain=.example.com; Path=/; Secure"));
+        assert!(jar.insert(&url, "c=3; Max-Age=0"));
+        assert!(!jar.insert(&url, "d=4; Domain=other.com"));
+        assert!(!jar.insert(&url, "e=5; Domain=com"));
+
+        let names = |url: &'static str| {
+            jar.cookies_for(&Uri::from_static(url))
+                .into_iter()
+                .map(|c| c.name)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(names("https://www.example.com/a/c"), ["a", "b"]);
+        assert_eq!(names("https://www.example.com/ab"), ["b"]);
+        assert_eq!(names("https://api.example.com/"), ["b"]);
+        assert!(names("http://api.example.com/").is_empty());
+
+        let mut headers = HeaderMap::new();
+        headers.insert(COOKIE, HeaderValue::from_static("x=0"));
+        jar.attach_request(&url, &mut headers);
+        assert_eq!(headers.get(COOKIE).unwrap(), "x=0; a=1; b=2");
+
+        // expired cookie removes stored one.
+        assert!(jar.insert(&url, "a=1; Expires=Thu, 01 Jan 1970 00:00:00 GMT"));
+        assert_eq!(names("https://www.example.com/a/c"), ["b"]);
+    }
+
+    #[test]
+    fn save_and_load() {
+        let jar = CookieJar::new();
+        let url = Uri::from_static("http://example.com/");
+        jar.insert(&url, "a=1; HttpOnly");
+        jar.insert(&url, "b=2; Domain=example.com; Max-Age=3600");
+
+        let mut buf = Vec::new();
+        jar.save(&mut buf).unwrap();
+
+        let loaded = CookieJar::new();
+        loaded.load(buf.as_slice()).unwrap();
+
+        let mut cookies = jar.cookies();
+        // expiry time is persisted in seconds.
+        for c in cookies.iter_mut() {
+            c.expires = c
+                .expires
+                .map(|e| UNIX_EPOCH + Duration::from_secs(e.duration_since(UNIX_EPOCH).unwrap().as_secs()));
+        }
+        assert_eq!(loaded.cookies(), cookies);
+    }
+
+    #[test]
+    fn oversized_expiry() {
+        let jar = CookieJar::new();
+        let url = Uri::from_static("http://example.com/");
+        assert!(jar.insert(&url, "a=1; Max-Age=9223372036854775807"));
+        assert_eq!(jar.cookies()[0].expires, None);
+
+        let loaded = CookieJar::new();
+        loaded
+            .load(&b"example.com\tFALSE\t/\tFALSE\t18446744073709551615\tb\t2\n"[..])
+            .unwrap();
+        assert_eq!(loaded.cookies()[0].expires, None);
+    }
+}
@@ -0,0 +1,188 @@
+//! transparent decompression of response body.
+
+use crate::{
+    http::header::{HeaderMap, HeaderValue, ACCEPT_ENCODING},
+    response::Response,
+};
+
+// insert Accept-Encoding header with enabled codecs when it's not present. return true when response
+// of the request should be decompressed.
+pub(crate) fn accept_encoding(headers: &mut HeaderMap) -> bool {
+    let encodings: &[&str] = &[
+        #[cfg(feature = "compress-gz")]
+        "gzip",
+        #[cfg(feature = "compress-de")]
+        "deflate",
+        #[cfg(feature = "compress-br")]
+        "br",
+    ];
+
+    if encodings.is_empty() || headers.contains_key(ACCEPT_ENCODING) {
+        return false;
+    }
+
+    let value = HeaderValue::from_str(&encodings.join(", ")).unwrap();
+    headers.insert(ACCEPT_ENCODING, value);
+
+    true
+}
+
+// wrap response body with decoder according to it's Content-Encoding header. response with
+// unsupported encoding is left as is.
+#[cfg(any(feature = "compress-br", feature = "compress-gz", feature = "compress-de"))]
+pub(crate) fn decompress(res: &mut Response<'_>) {
+    use core::{marker::PhantomData, mem};
+
+    use crate::{
+        body::ResponseBody,
+        http::{
+            header::{CONTENT_ENCODING, CONTENT_LENGTH},
+            StatusCode,
+        },
+    };
+
+    // response without body. headers describe the representation it refers to.
+    if matches!(res.status(), StatusCode::NO_CONTENT | StatusCode::NOT_MODIFIED) {
+        return;
+    }
+
+    match res.headers().get(CONTENT_ENCODING) {
+        Some(value) if !value.as_bytes().eq_ignore_ascii_case(b"identity") => {}
+        _ => return,
+    }
+
+    // construct decoder with placeholder body so the response stays intact on error.
+    let Ok(mut decoder) = http_encoding::try_decoder_from_headers(res.headers(), ResponseBody::Eof(PhantomData)) else {
+        return;
+    };
+
+    *decoder.get_mut() = mem::replace(res.body_mut(), ResponseBody::Eof(PhantomData));
+    *res.body_mut() = ResponseBody::Decompress(Box::new(decoder));
+
+    // headers describe encoded body and no longer apply.
+    res.headers_mut().remove(CONTENT_ENCODING);
+    res.headers_mut().remove(CONTENT_LENGTH);
+}
+
+#[cfg(not(any(feature = "compress-br", feature = "compress-gz", feature = "compress-de")))]
+pub(crate) fn decompress(_: &mut Response<'_>) {}
+
+#[cfg(all(test, feature = "compress-gz"))]
+mod test {
+    use std::{
+        io::{Read, Write},
+        net::TcpListener,
+        sync::mpsc,
+        thread,
+    };
+
+    use crate::{
+        http::{header::CONTENT_ENCODING, Method},
+        Client,
+    };
+
+    use super::*;
+
+    // gzip encoded "hello,world".
+    const GZIP: &[u8] = &[
+        31, 139, 8, 0, 0, 0, 0, 0, 2, 3, 203, 72, 205, 201, 201, 215, 41, 207, 47, 202, 73, 1, 0, 254, 209, 136, 122,
+        11, 0, 0, 0,
+    ];
+
+    // http/1 server responding every request with gzip encoded body. path of "/204" responds with
+    // 204 status code. Accept-Encoding header of every request is sent to the receiver.
+    fn serve() -> (String, mpsc::Receiver<Option<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (tx, rx) = mpsc::channel();
+
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+
+                let mut head = Vec::new();
+                let mut buf = [0; 1024];
+                while !head.ends_with(b"\r\n\r\n") {
+                    let n = stream.read(&mut buf).unwrap();
+                    head.extend_from_slice(&buf[..n]);
+                }
+                let head = String::from_utf8(head).unwrap();
+
+                let accept_encoding = head.lines().find_map(|line| {
+                    let (name, value) = line.split_once(':')?;
+                    name.eq_ignore_ascii_case("accept-encoding")
+                        .then(|| value.trim().to_owned())
+                });
+                tx.send(accept_encoding).unwrap();
+
+                let res = if head.starts_with("GET /204") {
+                    b"HTTP/1.1 204 No Content\r\ncontent-encoding: gzip\r\n\r\n".to_vec()
+                } else {
+                    let mut res = format!(
+                        "HTTP/1.1 200 OK\r\ncontent-encoding: gzip\r\ncontent-length: {}\r\n\r\n",
+                        GZIP.len()
+                    )
+                    .into_bytes();
+                    if !head.starts_with("HEAD") {
+                        res.extend_from_slice(GZIP);
+                    }
+                    res
+                };
+                stream.write_all(&res).unwrap();
+            }
+        });
+
+        (format!("http://{addr}"), rx)
+    }
+
+    #[tokio::test]
+    async fn gzip() {
+        let (url, rx) = serve();
+        let client = Client::new();
+
+        let res = client.get(&url).unwrap().send().await.unwrap();
+        assert_eq!(rx.recv().unwrap().as_deref(), Some("gzip"));
+        assert!(!res.headers().contains_key(CONTENT_ENCODING));
+        assert_eq!(res.string().await.unwrap(), "hello,world");
+    }
+
+    #[tokio::test]
+    async fn no_decompress() {
+        let (url, rx) = serve();
+        let client = Client::new();
+
+        let res = client.get(&url).unwrap().no_decompress().send().await.unwrap();
+        assert_eq!(rx.recv().unwrap(), None);
+        assert_eq!(res.headers().get(CONTENT_ENCODING).unwrap(), "gzip");
+        assert_eq!(res.body().await.unwrap(), GZIP);
+    }
+
+    #[tokio::test]
+    async fn user_accept_encoding() {
+        let (url, rx) = serve();
+        let client = Client::new();
+
+        let mut req = client.get(&url).unwrap();
+        req.headers_mut()
+            .insert(ACCEPT_ENCODING, HeaderValue::from_static("gzip"));
+
+        let res = req.send().await.unwrap();
+        assert_eq!(rx.recv().unwrap().as_deref(), Some("gzip"));
+        assert_eq!(res.headers().get(CONTENT_ENCODING).unwrap(), "gzip");
+        assert_eq!(res.body().await.unwrap(), GZIP);
+    }
+
+    #[tokio::test]
+    async fn no_body() {
+        let (url, rx) = serve();
+        let client = Client::new();
+
+        let res = client.get(&url).unwrap().method(Method::HEAD).send().await.unwrap();
+        assert_eq!(rx.recv().unwrap(), None);
+        assert_eq!(res.headers().get(CONTENT_ENCODING).unwrap(), "gzip");
+
+        let res = client.get(format!("{url}/204")).unwrap().send().await.unwrap();
+        assert_eq!(rx.recv().unwrap().as_deref(), Some("gzip"));
+        assert_eq!(res.headers().get(CONTENT_ENCODING).unwrap(), "gzip");
+    }
+}
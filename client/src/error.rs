@@ -1,6 +1,9 @@
 use std::{convert::Infallible, error, fmt, io, str};
 
-use xitca_http::{error::BodyError, http::uri};
+use xitca_http::{
+    error::BodyError,
+    http::{uri, StatusCode},
+};
 
 #[derive(Debug)]
 #[non_exhaustive]
@@ -23,6 +26,7 @@ pub enum Error {
     #[cfg(feature = "rustls")]
     Rustls(_rustls::RustlsError),
     Parse(ParseError),
+    Proxy(ProxyError),
 }
 
 impl fmt::Display for Error {
@@ -106,6 +110,20 @@ impl From<TimeoutError> for Error {
     }
 }
 
+#[derive(Debug)]
+pub enum ProxyError {
+    Tunnel(StatusCode),
+    Socks5Auth,
+    Socks5(u8),
+    Malformed,
+}
+
+impl From<ProxyError> for Error {
+    fn from(e: ProxyError) -> Self {
+        Self::Proxy(e)
+    }
+}
+
 #[derive(Debug)]
 pub enum ParseError {
     String(str::Utf8Error),
@@ -25,6 +25,7 @@ pub(crate) async fn send<S, B, E>(
     stream: &mut S,
     date: DateTimeHandle<'_>,
     mut req: http::Request<B>,
+    absolute_form: bool,
 ) -> Result<(http::Response<()>, BytesMut, TransferCoding, bool), Error>
 where
     S: AsyncRead + AsyncWrite + Unpin,
@@ -57,7 +58,7 @@ where
     let mut buf = BytesMut::new();
 
     // encode request head and return transfer encoding for request body
-    let encoder = ctx.encode_head(&mut buf, parts, &body)?;
+    let encoder = ctx.encode_head(&mut buf, parts, &body, absolute_form)?;
 
     // send request head for potential intermediate handling like expect header.
     stream.write_all_buf(&mut buf).await?;
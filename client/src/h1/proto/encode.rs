@@ -14,6 +14,7 @@ impl<const HEADER_LIMIT: usize> Context<'_, '_, HEADER_LIMIT> {
         buf: &mut BytesMut,
         parts: Parts,
         body: &B,
+        absolute_form: bool,
     ) -> Result<TransferCoding, ProtoError>
     where
         B: Stream,
@@ -24,9 +25,15 @@ impl<const HEADER_LIMIT: usize> Context<'_, '_, HEADER_LIMIT> {
         let extensions = parts.extensions;
         let version = parts.version;
 
-        // encode line of "Method PathQuery Version"
+        // encode line of "Method PathQuery Version". request to forward proxy uses full uri.
         let method = method.as_str().as_bytes();
-        let path_and_query = uri.path_and_query().map(|u| u.as_str()).unwrap_or("/").as_bytes();
+        let uri_str;
+        let path_and_query = if absolute_form {
+            uri_str = uri.to_string();
+            uri_str.as_bytes()
+        } else {
+            uri.path_and_query().map(|u| u.as_str()).unwrap_or("/").as_bytes()
+        };
         let version = match version {
             Version::HTTP_09 => b" HTTP/0.9",
             Version::HTTP_10 => b" HTTP/1.0",
@@ -6,6 +6,7 @@ mod client;
 mod connect;
 mod connection;
 mod date;
+mod decompress;
 mod pool;
 mod request;
 mod resolver;
@@ -26,13 +27,26 @@ mod h3;
 #[cfg(feature = "websocket")]
 pub mod ws;
 
+#[cfg(feature = "cookie")]
+pub mod cookie;
+
+#[cfg(feature = "multipart")]
+pub mod multipart;
+
 pub mod error;
+pub mod middleware;
+pub mod proxy;
+pub mod redirect;
 
 pub use self::builder::ClientBuilder;
 pub use self::client::Client;
 pub use self::resolver::Resolve;
+pub use self::response::Response;
 pub use self::tls::{connector::TlsConnect, stream::Io};
 
+// re-export service trait for middleware.
+pub use xitca_service::Service;
+
 // re-export http crate.
 pub use xitca_http::http;
 
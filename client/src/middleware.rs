@@ -0,0 +1,379 @@
+//! middleware of client.
+//!
+//! A middleware is a [Service] type receiving [ServiceRequest] and producing [Response]. It can
+//! transform the request and pass it to the next middleware through [ServiceRequest::next] and
+//! observe the response it returns. Middlewares are called in the order they are added to
+//! [ClientBuilder](crate::ClientBuilder) and the request is sent after the last one.
+//!
+//! # Examples:
+//! ```rust
+//! use xitca_client::{
+//!     error::Error,
+//!     middleware::{BoxFuture, ServiceRequest},
+//!     Client, Response, Service,
+//! };
+//!
+//! struct Logger;
+//!
+//! impl<'c> Service<ServiceRequest<'c>> for Logger {
+//!     type Response = Response<'c>;
+//!     type Error = Error;
+//!     type Future<'f> = BoxFuture<'f, Response<'c>> where Self: 'f, 'c: 'f;
+//!
+//!     fn call<'s>(&'s self, req: ServiceRequest<'c>) -> Self::Future<'s>
+//!     where
+//!         'c: 's,
+//!     {
+//!         Box::pin(async move {
+//!             let uri = req.req.uri().clone();
+//!             let res = req.next().await?;
+//!             println!("{uri}: {}", res.status());
+//!             Ok(res)
+//!         })
+//!     }
+//! }
+//!
+//! # fn resolve() {
+//! let client = Client::builder().middleware(Logger).finish();
+//! # }
+//! ```
+
+use std::{future::Future, pin::Pin, time::Duration};
+
+use xitca_service::Service;
+
+use crate::{
+    client::Client,
+    error::Error,
+    http::{self, header::HeaderMap, Method, StatusCode},
+    response::Response,
+};
+
+pub use crate::body::RequestBody;
+
+/// Boxed future type of middleware.
+pub type BoxFuture<'f, T> = Pin<Box<dyn Future<Output = Result<T, Error>> + Send + 'f>>;
+
+/// Request passed through middlewares.
+pub struct ServiceRequest<'c> {
+    /// HTTP request to be sent.
+    pub req: http::Request<RequestBody<'c>>,
+    /// Client sending the request.
+    pub client: &'c Client,
+    /// Timeout of waiting for response head.
+    pub timeout: Duration,
+    next: usize,
+}
+
+impl<'c> ServiceRequest<'c> {
+    pub(crate) fn new(req: http::Request<RequestBody<'c>>, client: &'c Client, timeout: Duration) -> Self {
+        Self {
+            req,
+            client,
+            timeout,
+            next: 0,
+        }
+    }
+
+    /// Pass request to next middleware. Request is sent when there is no middleware left.
+    pub fn next(mut self) -> impl Future<Output = Result<Response<'c>, Error>> + Send + 'c {
+        let client = self.client;
+        match client.middlewares.get(self.next) {
+            Some(middleware) => {
+                self.next += 1;
+                middleware.call(self)
+            }
+            None => Box::pin(crate::request::send(self.req, client, self.timeout)) as BoxFuture<'c, _>,
+        }
+    }
+
+    /// Clone request when it's body is not a stream. The clone would be passed to the same next
+    /// middleware.
+    pub fn try_clone(&self) -> Option<Self> {
+        let body = self.req.body().try_clone()?;
+
+        let mut req = http::Request::new(body);
+        *req.method_mut() = self.req.method().clone();
+        *req.uri_mut() = self.req.uri().clone();
+        *req.version_mut() = self.req.version();
+        *req.headers_mut() = self.req.headers().clone();
+
+        Some(Self {
+            req,
+            client: self.client,
+            timeout: self.timeout,
+            next: self.next,
+        })
+    }
+}
+
+// object safe counterpart of middleware service.
+pub(crate) trait ServiceDyn: Send + Sync {
+    fn call<'c>(&'c self, req: ServiceRequest<'c>) -> BoxFuture<'c, Response<'c>>;
+}
+
+impl<S> ServiceDyn for S
+where
+    S: for<'c> Service<ServiceRequest<'c>, Response = Response<'c>, Error = Error> + Send + Sync,
+    for<'c> <S as Service<ServiceRequest<'c>>>::Future<'c>: Send,
+{
+    fn call<'c>(&'c self, req: ServiceRequest<'c>) -> BoxFuture<'c, Response<'c>> {
+        Box::pin(Service::call(self, req))
+    }
+}
+
+/// Middleware retrying failed request with exponential backoff.
+///
+/// Only requests with idempotent methods (GET, HEAD, OPTIONS, TRACE, PUT and DELETE) and
+/// non-streaming body are retried. A request is retried when it fails with io, timeout or
+/// protocol error, or when response has status code of 502, 503 or 504.
+///
+/// Default to retry at most 3 times with backoff starting from 100 milliseconds and capped at 10
+/// seconds.
+#[derive(Clone, Debug)]
+pub struct Retry {
+    max_retries: usize,
+    backoff: Duration,
+    max_backoff: Duration,
+}
+
+impl Default for Retry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Retry {
+    /// Construct retry middleware with default setting.
+    pub const fn new() -> Self {
+        Self {
+            max_retries: 3,
+            backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(10),
+        }
+    }
+
+    /// Set max number of retries.
+    pub fn max_retries(mut self, max: usize) -> Self {
+        self.max_retries = max;
+        self
+    }
+
+    /// Set backoff of first retry. Backoff is doubled on each retry until it reaches `max`.
+    pub fn backoff(mut self, backoff: Duration, max: Duration) -> Self {
+        self.backoff = backoff;
+        self.max_backoff = max;
+        self
+    }
+
+    fn backoff_of(&self, retries: usize) -> Duration {
+        let factor = 1u32.checked_shl(retries as u32).unwrap_or(u32::MAX);
+        self.backoff.saturating_mul(factor).min(self.max_backoff)
+    }
+}
+
+impl<'c> Service<ServiceRequest<'c>> for Retry {
+    type Response = Response<'c>;
+    type Error = Error;
+    type Future<'f> = BoxFuture<'f, Response<'c>> where Self: 'f, 'c: 'f;
+
+    fn call<'s>(&'s self, mut req: ServiceRequest<'c>) -> Self::Future<'s>
+    where
+        'c: 's,
+    {
+        Box::pin(async move {
+            let idempotent = matches!(
+                *req.req.method(),
+                Method::GET | Method::HEAD | Method::OPTIONS | Method::TRACE | Method::PUT | Method::DELETE
+            );
+
+            let mut retries = 0;
+
+            loop {
+                let retry = match idempotent && retries < self.max_retries {
+                    true => req.try_clone(),
+                    false => None,
+                };
+
+                let res = req.next().await;
+
+                let Some(retry) = retry else { return res };
+
+                match res {
+                    Ok(mut res)
+                        if matches!(
+                            res.status(),
+                            StatusCode::BAD_GATEWAY | StatusCode::SERVICE_UNAVAILABLE | StatusCode::GATEWAY_TIMEOUT
+                        ) =>
+                    {
+                        // body of response is not read and connection can't be reused.
+                        res.res.body_mut().destroy_on_drop();
+                    }
+                    Err(ref e) if is_retryable(e) => {}
+                    res => return res,
+                }
+
+                tokio::time::sleep(self.backoff_of(retries)).await;
+
+                retries += 1;
+                req = retry;
+            }
+        })
+    }
+}
+
+fn is_retryable(e: &Error) -> bool {
+    match *e {
+        Error::Io(_) | Error::Timeout(_) | Error::Resolve => true,
+        #[cfg(feature = "http1")]
+        Error::H1(_) => true,
+        #[cfg(feature = "http2")]
+        Error::H2(_) => true,
+        #[cfg(feature = "http3")]
+        Error::H3(_) => true,
+        _ => false,
+    }
+}
+
+/// Middleware adding default headers to request. Headers already in request are not overridden.
+#[derive(Clone, Debug)]
+pub struct DefaultHeaders {
+    headers: HeaderMap,
+}
+
+impl DefaultHeaders {
+    /// Construct middleware with given default headers.
+    pub fn new(headers: HeaderMap) -> Self {
+        Self { headers }
+    }
+}
+
+impl<'c> Service<ServiceRequest<'c>> for DefaultHeaders {
+    type Response = Response<'c>;
+    type Error = Error;
+    type Future<'f> = BoxFuture<'f, Response<'c>> where Self: 'f, 'c: 'f;
+
+    fn call<'s>(&'s self, mut req: ServiceRequest<'c>) -> Self::Future<'s>
+    where
+        'c: 's,
+    {
+        let headers = req.req.headers_mut();
+        for name in self.headers.keys() {
+            if !headers.contains_key(name) {
+                for value in self.headers.get_all(name) {
+                    headers.append(name.clone(), value.clone());
+                }
+            }
+        }
+
+        Box::pin(req.next())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        convert::Infallible,
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+    };
+
+    use futures_util::StreamExt;
+
+    use crate::{
+        bytes::Bytes,
+        http::header::{HeaderValue, USER_AGENT},
+    };
+
+    use super::*;
+
+    // middleware recording request and failing it without sending.
+    struct Fail(Arc<AtomicUsize>);
+
+    impl<'c> Service<ServiceRequest<'c>> for Fail {
+        type Response = Response<'c>;
+        type Error = Error;
+        type Future<'f> = BoxFuture<'f, Response<'c>> where Self: 'f, 'c: 'f;
+
+        fn call<'s>(&'s self, req: ServiceRequest<'c>) -> Self::Future<'s>
+        where
+            'c: 's,
+        {
+            assert_eq!(req.req.headers().get(USER_AGENT).unwrap(), "xitca");
+            self.0.fetch_add(1, Ordering::SeqCst);
+            Box::pin(async { Err(Error::Resolve) })
+        }
+    }
+
+    #[tokio::test]
+    async fn retry_with_default_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert(USER_AGENT, HeaderValue::from_static("xitca"));
+
+        let count = Arc::new(AtomicUsize::new(0));
+
+        let client = Client::builder()
+            .middleware(DefaultHeaders::new(headers))
+            .middleware(Retry::new().max_retries(2).backoff(Duration::ZERO, Duration::ZERO))
+            .middleware(Fail(count.clone()))
+            .finish();
+
+        let err = client.get("http://localhost").unwrap().send().await.unwrap_err();
+        assert!(matches!(err, Error::Resolve));
+        assert_eq!(count.load(Ordering::SeqCst), 3);
+
+        // non idempotent method is not retried.
+        let err = client.post("http://localhost").unwrap().send().await.unwrap_err();
+        assert!(matches!(err, Error::Resolve));
+        assert_eq!(count.load(Ordering::SeqCst), 4);
+    }
+
+    // middleware reading streaming body of request and failing it without sending.
+    struct ReadBody;
+
+    impl<'c> Service<ServiceRequest<'c>> for ReadBody {
+        type Response = Response<'c>;
+        type Error = Error;
+        type Future<'f> = BoxFuture<'f, Response<'c>> where Self: 'f, 'c: 'f;
+
+        fn call<'s>(&'s self, mut req: ServiceRequest<'c>) -> Self::Future<'s>
+        where
+            'c: 's,
+        {
+            Box::pin(async move {
+                assert!(req.try_clone().is_none());
+
+                let mut body = Vec::new();
+                while let Some(chunk) = req.req.body_mut().next().await {
+                    body.extend_from_slice(&chunk.unwrap());
+                }
+                assert_eq!(body, b"hello,world");
+
+                Err(Error::Resolve)
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn non_send_stream_body() {
+        let client = Client::builder().middleware(ReadBody).finish();
+
+        // body holding Rc is not Send.
+        let rc = std::rc::Rc::new(());
+        let body = futures_util::stream::iter(["hello,", "world"]).map(move |chunk| {
+            let _rc = &rc;
+            Ok::<_, Infallible>(Bytes::from(chunk))
+        });
+
+        let err = client
+            .post("http://localhost")
+            .unwrap()
+            .stream(body)
+            .send()
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::Resolve));
+    }
+}
@@ -0,0 +1,417 @@
+//! multipart/form-data request body.
+//!
+//! # Examples:
+//! ```rust
+//! use xitca_client::{
+//!     bytes::Bytes,
+//!     multipart::{Form, Part},
+//!     Client,
+//! };
+//!
+//! # async fn upload(client: &Client) -> Result<(), xitca_client::error::Error> {
+//! let form = Form::new()
+//!     .text("title", "hello")
+//!     .part(
+//!         "attachment",
+//!         Part::bytes(Bytes::from_static(b"hello,world!"))
+//!             .file_name("hello.txt")
+//!             .content_type("text/plain"),
+//!     )
+//!     .file("image", "./image.png")
+//!     .await?;
+//!
+//! let res = client.post("https://example.com/upload")?.multipart(form).send().await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use core::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use std::{
+    borrow::Cow,
+    collections::{hash_map::RandomState, VecDeque},
+    fmt,
+    hash::{BuildHasher, Hasher},
+    io,
+    path::Path,
+};
+
+use futures_core::stream::Stream;
+
+use crate::{
+    body::{BodyError, RequestBody},
+    bytes::{BufMut, Bytes, BytesMut},
+    http::header::{HeaderMap, HeaderName, HeaderValue, CONTENT_TYPE},
+};
+
+/// A multipart/form-data request body builder.
+///
+/// Parts are sent in the order they are added. Every part is separated by a generated boundary.
+pub struct Form<'a> {
+    boundary: String,
+    parts: Vec<(Cow<'static, str>, Part<'a>)>,
+}
+
+impl Default for Form<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> Form<'a> {
+    /// Construct an empty form with generated boundary.
+    pub fn new() -> Self {
+        Self {
+            boundary: boundary(),
+            parts: Vec::new(),
+        }
+    }
+
+    /// Boundary separating parts of the form.
+    pub fn boundary(&self) -> &str {
+        &self.boundary
+    }
+
+    /// Add a text field to the form.
+    pub fn text<N, V>(self, name: N, value: V) -> Self
+    where
+        N: Into<Cow<'static, str>>,
+        V: Into<Cow<'static, str>>,
+    {
+        let value = match value.into() {
+            Cow::Borrowed(value) => Bytes::from_static(value.as_bytes()),
+            Cow::Owned(value) => Bytes::from(value),
+        };
+        self.part(name, Part::bytes(value))
+    }
+
+    /// Add a custom part to the form.
+    pub fn part<N>(mut self, name: N, part: Part<'a>) -> Self
+    where
+        N: Into<Cow<'static, str>>,
+    {
+        self.parts.push((name.into(), part));
+        self
+    }
+
+    /// Add a file part to the form. The file content is streamed from disk when sending request.
+    ///
+    /// See [Part::file] for detail.
+    pub async fn file<N, P>(self, name: N, path: P) -> io::Result<Self>
+    where
+        N: Into<Cow<'static, str>>,
+        P: AsRef<Path>,
+    {
+        let part = Part::file(path).await?;
+        Ok(self.part(name, part))
+    }
+
+    pub(crate) fn content_type(&self) -> HeaderValue {
+        let value = format!("multipart/form-data; boundary={}", self.boundary);
+        HeaderValue::from_str(&value).unwrap()
+    }
+
+    pub(crate) fn into_stream(self) -> FormStream<'a> {
+        let mut bodies = VecDeque::with_capacity(self.parts.len() * 2 + 1);
+
+        for (name, part) in self.parts {
+            bodies.push_back(RequestBody::Bytes(part.head(&self.boundary, &name)));
+            bodies.push_back(part.body);
+            bodies.push_back(RequestBody::Bytes(Bytes::from_static(b"\r\n")));
+        }
+
+        bodies.push_back(RequestBody::Bytes(Bytes::from(format!("--{}--\r\n", self.boundary))));
+
+        FormStream { bodies }
+    }
+}
+
+impl fmt::Debug for Form<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Form")
+            .field("boundary", &self.boundary)
+            .field("parts", &self.parts)
+            .finish()
+    }
+}
+
+/// A single part of [Form].
+pub struct Part<'a> {
+    body: RequestBody<'a>,
+    file_name: Option<Cow<'static, str>>,
+    content_type: Option<Cow<'static, str>>,
+    headers: HeaderMap,
+}
+
+impl<'a> Part<'a> {
+    fn new(body: RequestBody<'a>) -> Self {
+        Self {
+            body,
+            file_name: None,
+            content_type: None,
+            headers: HeaderMap::new(),
+        }
+    }
+
+    /// Construct a part from pre allocated bytes.
+    ///
+    /// Input type must implement [From] trait with [Bytes].
+    pub fn bytes<B>(bytes: B) -> Self
+    where
+        Bytes: From<B>,
+    {
+        Self::new(RequestBody::Bytes(Bytes::from(bytes)))
+    }
+
+    /// Construct a part from streaming type.
+    pub fn stream<S, E>(stream: S) -> Self
+    where
+        S: Stream<Item = Result<Bytes, E>> + Send + 'a,
+        BodyError: From<E>,
+    {
+        Self::new(RequestBody::stream(stream))
+    }
+
+    /// Construct a part from file on disk. The file is opened eagerly and it's content is streamed
+    /// when sending request.
+    ///
+    /// File name is set to the last component of path and content type is set to
+    /// `application/octet-stream`. Both can be overridden with [Part::file_name] and
+    /// [Part::content_type].
+    pub async fn file<P>(path: P) -> io::Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        let file = tokio::fs::File::open(path).await?;
+
+        let mut part = Self::stream(tokio_util::io::ReaderStream::new(file)).content_type("application/octet-stream");
+
+        if let Some(name) = path.file_name() {
+            part = part.file_name(name.to_string_lossy().into_owned());
+        }
+
+        Ok(part)
+    }
+
+    /// Set file name of the part.
+    pub fn file_name<N>(mut self, name: N) -> Self
+    where
+        N: Into<Cow<'static, str>>,
+    {
+        self.file_name = Some(name.into());
+        self
+    }
+
+    /// Set content type of the part. Line breaks in it are escaped.
+    pub fn content_type<M>(mut self, mime: M) -> Self
+    where
+        M: Into<Cow<'static, str>>,
+    {
+        self.content_type = Some(mime.into());
+        self
+    }
+
+    /// Add a custom header to the part.
+    pub fn header(mut self, name: HeaderName, value: HeaderValue) -> Self {
+        self.headers.append(name, value);
+        self
+    }
+
+    /// Returns part's mutable headers.
+    pub fn headers_mut(&mut self) -> &mut HeaderMap {
+        &mut self.headers
+    }
+
+    fn head(&self, boundary: &str, name: &str) -> Bytes {
+        let mut buf = BytesMut::with_capacity(128);
+
+        buf.put_slice(b"--");
+        buf.put_slice(boundary.as_bytes());
+        buf.put_slice(b"\r\ncontent-disposition: form-data; name=\"");
+        put_quoted(&mut buf, name);
+        buf.put_slice(b"\"");
+
+        if let Some(ref file_name) = self.file_name {
+            buf.put_slice(b"; filename=\"");
+            put_quoted(&mut buf, file_name);
+            buf.put_slice(b"\"");
+        }
+
+        // content type set by builder method takes precedence.
+        if let Some(ref content_type) = self.content_type {
+            buf.put_slice(b"\r\ncontent-type: ");
+            put_line(&mut buf, content_type);
+        }
+
+        for (name, value) in self.headers.iter() {
+            if name == CONTENT_TYPE && self.content_type.is_some() {
+                continue;
+            }
+            buf.put_slice(b"\r\n");
+            buf.put_slice(name.as_str().as_bytes());
+            buf.put_slice(b": ");
+            buf.put_slice(value.as_bytes());
+        }
+
+        buf.put_slice(b"\r\n\r\n");
+
+        buf.freeze()
+    }
+}
+
+impl fmt::Debug for Part<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Part")
+            .field("body", &self.body)
+            .field("file_name", &self.file_name)
+            .field("content_type", &self.content_type)
+            .field("headers", &self.headers)
+            .finish()
+    }
+}
+
+/// Streaming body of [Form].
+pub struct FormStream<'a> {
+    bodies: VecDeque<RequestBody<'a>>,
+}
+
+impl Stream for FormStream<'_> {
+    type Item = Result<Bytes, BodyError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        while let Some(body) = this.bodies.front_mut() {
+            match Pin::new(body).poll_next(cx) {
+                Poll::Ready(None) => {
+                    this.bodies.pop_front();
+                }
+                res => return res,
+            }
+        }
+        Poll::Ready(None)
+    }
+
+    // form has exact size when none of it's part is a stream of unknown size.
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.bodies
+            .iter()
+            .try_fold(0, |size, body| body.size_hint().1.map(|s| size + s))
+            .map(|size| (size, Some(size)))
+            .unwrap_or((0, None))
+    }
+}
+
+// escape quote and line break in name and file name of content-disposition.
+fn put_quoted(buf: &mut BytesMut, value: &str) {
+    for b in value.bytes() {
+        match b {
+            b'"' => buf.put_slice(b"%22"),
+            b'\r' => buf.put_slice(b"%0D"),
+            b'\n' => buf.put_slice(b"%0A"),
+            b => buf.put_u8(b),
+        }
+    }
+}
+
+// escape line break in header value so it can not inject header or break framing of part.
+fn put_line(buf: &mut BytesMut, value: &str) {
+    for b in value.bytes() {
+        match b {
+            b'\r' => buf.put_slice(b"%0D"),
+            b'\n' => buf.put_slice(b"%0A"),
+            b => buf.put_u8(b),
+        }
+    }
+}
+
+fn boundary() -> String {
+    let mut hasher = RandomState::new().build_hasher();
+    let a = hasher.finish();
+    hasher.write_u64(a);
+    let b = hasher.finish();
+    format!("{a:016x}{b:016x}")
+}
+
+#[cfg(test)]
+mod test {
+    use futures_util::StreamExt;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn form_stream() {
+        let form = Form::new()
+            .text("a", "1")
+            .part(
+                "b\"",
+                Part::bytes("2")
+                    .file_name("b.txt")
+                    .content_type("text/plain")
+                    .header(HeaderName::from_static("x-custom"), HeaderValue::from_static("foo")),
+            )
+            .part(
+                "c",
+                Part::stream({
+                    let mut item = Some(Ok::<_, BodyError>(Bytes::from_static(b"3")));
+                    futures_util::stream::poll_fn(move |_| Poll::Ready(item.take()))
+                }),
+            );
+
+        let boundary = form.boundary().to_owned();
+        assert!(form
+            .content_type()
+            .to_str()
+            .unwrap()
+            .ends_with(&format!("boundary={boundary}")));
+
+        let mut stream = form.into_stream();
+        assert_eq!(stream.size_hint(), (0, None));
+
+        let mut body = Vec::new();
+        while let Some(bytes) = stream.next().await {
+            body.extend_from_slice(&bytes.unwrap());
+        }
+
+        let expected = format!(
+            "--{boundary}\r\ncontent-disposition: form-data; name=\"a\"\r\n\r\n1\r\n\
+             --{boundary}\r\ncontent-disposition: form-data; name=\"b%22\"; filename=\"b.txt\"\r\n\
+             content-type: text/plain\r\nx-custom: foo\r\n\r\n2\r\n\
+             --{boundary}\r\ncontent-disposition: form-data; name=\"c\"\r\n\r\n3\r\n\
+             --{boundary}--\r\n"
+        );
+        assert_eq!(String::from_utf8(body).unwrap(), expected);
+
+        let mut stream = Form::new().text("a", "1").into_stream();
+        let (size, _) = stream.size_hint();
+        assert_eq!(stream.size_hint(), (size, Some(size)));
+
+        let mut len = 0;
+        while let Some(bytes) = stream.next().await {
+            len += bytes.unwrap().len();
+        }
+        assert_eq!(len, size);
+    }
+
+    #[tokio::test]
+    async fn content_type_line_break() {
+        let form = Form::new().part("a", Part::bytes("1").content_type("text/plain\r\nx-injected: 1"));
+        let boundary = form.boundary().to_owned();
+
+        let mut stream = form.into_stream();
+        let mut body = Vec::new();
+        while let Some(bytes) = stream.next().await {
+            body.extend_from_slice(&bytes.unwrap());
+        }
+
+        let expected = format!(
+            "--{boundary}\r\ncontent-disposition: form-data; name=\"a\"\r\n\
+             content-type: text/plain%0D%0Ax-injected: 1\r\n\r\n1\r\n\
+             --{boundary}--\r\n"
+        );
+        assert_eq!(String::from_utf8(body).unwrap(), expected);
+    }
+}
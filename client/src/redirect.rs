@@ -0,0 +1,205 @@
+//! redirect policy of client.
+
+use std::sync::Arc;
+
+use crate::http::{
+    header::{HeaderMap, LOCATION},
+    uri::{Scheme, Uri},
+    StatusCode,
+};
+
+/// Policy of following redirect response.
+///
+/// A redirect response that is not followed is returned as is. Default policy follows at most 10
+/// redirects.
+///
+/// # Examples:
+/// ```rust
+/// use xitca_client::{redirect::RedirectPolicy, Client};
+///
+/// let policy = RedirectPolicy::limited(3)
+///     .same_origin()
+///     .predicate(|attempt| attempt.next().path() != "/login");
+///
+/// # fn resolve(policy: RedirectPolicy) {
+/// let client = Client::builder().redirect_policy(policy).finish();
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct RedirectPolicy {
+    max: usize,
+    same_origin: bool,
+    predicate: Option<Predicate>,
+}
+
+type Predicate = Arc<dyn Fn(&Attempt<'_>) -> bool + Send + Sync>;
+
+impl Default for RedirectPolicy {
+    fn default() -> Self {
+        Self::limited(10)
+    }
+}
+
+impl RedirectPolicy {
+    /// Do not follow any redirect.
+    pub fn none() -> Self {
+        Self::limited(0)
+    }
+
+    /// Follow at most `max` redirects.
+    pub fn limited(max: usize) -> Self {
+        Self {
+            max,
+            same_origin: false,
+            predicate: None,
+        }
+    }
+
+    /// Only follow redirect to the same origin (scheme, host and port) of the redirecting request.
+    pub fn same_origin(mut self) -> Self {
+        self.same_origin = true;
+        self
+    }
+
+    /// Only follow redirect when given function returns true.
+    pub fn predicate<F>(mut self, func: F) -> Self
+    where
+        F: Fn(&Attempt<'_>) -> bool + Send + Sync + 'static,
+    {
+        self.predicate = Some(Arc::new(func));
+        self
+    }
+
+    pub(crate) fn is_none(&self) -> bool {
+        self.max == 0
+    }
+
+    pub(crate) fn follow(&self, attempt: &Attempt<'_>) -> bool {
+        attempt.previous.len() < self.max
+            && (!self.same_origin || is_same_origin(attempt.uri, attempt.next))
+            && self.predicate.as_ref().map_or(true, |func| func(attempt))
+    }
+}
+
+/// A redirect about to be followed.
+pub struct Attempt<'a> {
+    pub(crate) status: StatusCode,
+    pub(crate) uri: &'a Uri,
+    pub(crate) next: &'a Uri,
+    pub(crate) previous: &'a [Uri],
+}
+
+impl Attempt<'_> {
+    /// Status code of the redirect response.
+    #[inline]
+    pub fn status(&self) -> StatusCode {
+        self.status
+    }
+
+    /// URL of the request that is redirected.
+    #[inline]
+    pub fn uri(&self) -> &Uri {
+        self.uri
+    }
+
+    /// URL the request is redirected to.
+    #[inline]
+    pub fn next(&self) -> &Uri {
+        self.next
+    }
+
+    /// URLs of previous requests that are redirected before current one.
+    #[inline]
+    pub fn previous(&self) -> &[Uri] {
+        self.previous
+    }
+}
+
+// resolve target of redirect response against the url of redirected request.
+pub(crate) fn location(status: StatusCode, headers: &HeaderMap, base: &Uri) -> Option<Uri> {
+    if !matches!(
+        status,
+        StatusCode::MOVED_PERMANENTLY
+            | StatusCode::FOUND
+            | StatusCode::SEE_OTHER
+            | StatusCode::TEMPORARY_REDIRECT
+            | StatusCode::PERMANENT_REDIRECT
+    ) {
+        return None;
+    }
+
+    let location = headers.get(LOCATION)?.to_str().ok()?;
+    // fragment is not sent to server.
+    let location = location.split('#').next().unwrap_or(location);
+
+    let scheme = base.scheme().unwrap_or(&Scheme::HTTP);
+
+    if location.starts_with("//") {
+        return format!("{scheme}:{location}").parse().ok();
+    }
+
+    // absolute url.
+    if let Some((s, _)) = location.split_once("://") {
+        if !s.is_empty()
+            && s.bytes()
+                .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'+' | b'-' | b'.'))
+        {
+            return location.parse().ok();
+        }
+    }
+
+    let authority = base.authority()?;
+
+    let path = if location.starts_with('/') {
+        location.into()
+    } else if location.starts_with('?') {
+        format!("{}{location}", base.path())
+    } else {
+        let path = base.path();
+        let dir = path.rfind('/').map(|i| &path[..=i]).unwrap_or("/");
+        format!("{dir}{location}")
+    };
+
+    format!("{scheme}://{authority}{path}").parse().ok()
+}
+
+pub(crate) fn is_same_origin(a: &Uri, b: &Uri) -> bool {
+    a.scheme() == b.scheme() && a.host() == b.host() && port(a) == port(b)
+}
+
+fn port(uri: &Uri) -> Option<u16> {
+    uri.port_u16().or_else(|| match uri.scheme_str() {
+        Some("http" | "ws") => Some(80),
+        Some("https" | "wss") => Some(443),
+        _ => None,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::http::HeaderValue;
+
+    use super::*;
+
+    #[test]
+    fn resolve_location() {
+        let base = Uri::from_static("https://example.com/a/b?c=d");
+
+        let resolve = |value: &'static str| {
+            let mut headers = HeaderMap::new();
+            headers.insert(LOCATION, HeaderValue::from_static(value));
+            location(StatusCode::FOUND, &headers, &base).unwrap().to_string()
+        };
+
+        assert_eq!(resolve("http://foo.com/bar"), "http://foo.com/bar");
+        assert_eq!(resolve("//foo.com/bar"), "https://foo.com/bar");
+        assert_eq!(resolve("/bar#frag"), "https://example.com/bar");
+        assert_eq!(resolve("bar"), "https://example.com/a/bar");
+        assert_eq!(resolve("?e=f"), "https://example.com/a/b?e=f");
+
+        assert!(location(StatusCode::OK, &HeaderMap::new(), &base).is_none());
+
+        assert!(is_same_origin(&base, &Uri::from_static("https://example.com:443/")));
+        assert!(!is_same_origin(&base, &Uri::from_static("http://example.com/")));
+    }
+}
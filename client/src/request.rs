@@ -1,19 +1,32 @@
-use std::time::Duration;
+use std::{
+    future::{poll_fn, Future},
+    pin::pin,
+    time::Duration,
+};
 
 use futures_core::Stream;
 use tokio::time::Instant;
 
+use xitca_http::body::none_body_hint;
+
 use crate::{
-    body::{BodyError, Once},
+    body::{BodyError, Once, RequestBody},
     bytes::Bytes,
     client::Client,
     connect::Connect,
+    connection::ConnectionKey,
     error::Error,
     http::{
         self, const_header_value,
-        header::{HeaderMap, HeaderValue, CONTENT_LENGTH, CONTENT_TYPE},
-        Extensions, Method, Version,
+        header::{
+            HeaderMap, HeaderValue, AUTHORIZATION, CONTENT_ENCODING, CONTENT_LENGTH, CONTENT_TYPE, COOKIE, HOST,
+            PROXY_AUTHORIZATION, TRANSFER_ENCODING,
+        },
+        Extensions, Method, StatusCode, Version,
     },
+    middleware::ServiceRequest,
+    proxy::Proxy,
+    redirect::{self, Attempt},
     response::Response,
     uri::Uri,
 };
@@ -30,6 +43,10 @@ pub struct Request<'a, B = Once<Bytes>> {
     /// Request level timeout setting. When Some(Duration) would override
     /// timeout configuration from Client.
     timeout: Duration,
+    /// Request body in bytes that can be sent again when following redirect.
+    replay: Option<Bytes>,
+    /// Decompress response body according to it's Content-Encoding header.
+    decompress: bool,
 }
 
 impl<'a, B> Request<'a, B> {
@@ -38,6 +55,8 @@ impl<'a, B> Request<'a, B> {
             req,
             client,
             timeout: client.timeout_config.request_timeout,
+            replay: None,
+            decompress: true,
         }
     }
 
@@ -90,6 +109,16 @@ impl<'a, B> Request<'a, B> {
         self
     }
 
+    /// Disable decompression of response body for this request.
+    ///
+    /// By default `Accept-Encoding` header with codecs enabled by `compress-*` features is sent and
+    /// response body is decompressed according to it's `Content-Encoding` header. Decompression is
+    /// also skipped when `Accept-Encoding` header is set manually.
+    pub fn no_decompress(mut self) -> Self {
+        self.decompress = false;
+        self
+    }
+
     /// Use text(utf-8 encoded) as request body.
     ///
     /// [CONTENT_TYPE] header would be set with value: `text/plain; charset=utf-8`.
@@ -113,6 +142,37 @@ impl<'a, B> Request<'a, B> {
         Ok(self.body(body))
     }
 
+    #[cfg(feature = "form")]
+    /// Use url encoded form as request body.
+    ///
+    /// [CONTENT_TYPE] header would be set with value: `application/x-www-form-urlencoded`.
+    pub fn form<I, K, V>(mut self, form: I) -> Request<'a>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        let body = form_urlencoded::Serializer::new(String::new())
+            .extend_pairs(form)
+            .finish();
+
+        self.headers_mut()
+            .insert(CONTENT_TYPE, const_header_value::FORM_URLENCODED);
+
+        self.body(body)
+    }
+
+    #[cfg(feature = "multipart")]
+    /// Use multipart form as request body.
+    ///
+    /// [CONTENT_TYPE] header would be set with value: `multipart/form-data` and boundary of the form.
+    /// See [Form](crate::multipart::Form) for detail.
+    pub fn multipart(mut self, form: crate::multipart::Form<'a>) -> Request<'a, crate::multipart::FormStream<'a>> {
+        self.headers_mut().insert(CONTENT_TYPE, form.content_type());
+        self.headers_mut().remove(CONTENT_LENGTH);
+        self.map_body(move |_| form.into_stream())
+    }
+
     /// Use pre allocated bytes as request body.
     ///
     /// Input type must implement [From] trait with [Bytes].
@@ -123,7 +183,9 @@ impl<'a, B> Request<'a, B> {
         let bytes = Bytes::from(body);
         self.headers_mut()
             .insert(CONTENT_LENGTH, HeaderValue::from(bytes.len()));
-        self.map_body(move |_| Once::new(bytes))
+        let mut req = self.map_body(|_| Once::new(bytes.clone()));
+        req.replay = Some(bytes);
+        req
     }
 
     /// Use streaming type as request body.
@@ -142,139 +204,364 @@ impl<'a, B> Request<'a, B> {
         B1: Stream<Item = Result<Bytes, E1>>,
         BodyError: From<E1>,
     {
-        let Self { req, client, timeout } = self;
+        let Self {
+            req,
+            client,
+            timeout,
+            decompress,
+            ..
+        } = self;
         let (parts, body_old) = req.into_parts();
 
         let body = f(body_old);
         let req = http::Request::from_parts(parts, body);
 
-        Request::new(req, client).timeout(timeout)
+        let mut req = Request::new(req, client).timeout(timeout);
+        req.decompress = decompress;
+        req
     }
 
     /// Send the request and wait for response asynchronously.
-    #[allow(unused_variables, unused_mut)]
+    ///
+    /// Request is passed through [middleware](crate::middleware) of [Client] before sent.
+    ///
+    /// Response body is decompressed unless it's disabled by [Request::no_decompress]. Response of
+    /// HEAD request and response with 204 or 304 status code are left as is.
+    ///
+    /// Redirect response is followed according to [RedirectPolicy](crate::redirect::RedirectPolicy)
+    /// of [Client]. Request with streaming body is not resent on 307 and 308 redirect and the redirect
+    /// response is returned as is.
     pub async fn send<E>(self) -> Result<Response<'a>, Error>
     where
         B: Stream<Item = Result<Bytes, E>>,
         BodyError: From<E>,
     {
         let Self {
-            mut req,
+            req,
             client,
             timeout,
+            replay,
+            decompress,
         } = self;
 
-        let uri = Uri::try_parse(req.uri())?;
-
-        // Try to grab a connection from pool.
-        let mut conn = client.pool.acquire(&uri).await?;
+        let (mut parts, body) = req.into_parts();
 
-        let conn_is_none = conn.is_none();
+        // response of HEAD request has no body to decompress.
+        let decompress =
+            decompress && parts.method != Method::HEAD && crate::decompress::accept_encoding(&mut parts.headers);
 
-        // setup timer according to outcome and timeout configs.
-        let dur = if conn_is_none {
-            client.timeout_config.resolve_timeout
-        } else {
-            timeout
+        // body can only be sent again when it's empty or in bytes.
+        let mut replay = match replay {
+            Some(bytes) => Some(RequestBody::Bytes(bytes)),
+            None if body.size_hint() == none_body_hint() => Some(RequestBody::None),
+            None => None,
         };
 
-        // heap allocate timer so it can be moved to Response type afterwards
-        let mut timer = Box::pin(tokio::time::sleep(dur));
+        let req = http::Request::from_parts(parts, body);
 
-        // Nothing in the pool. construct new connection and add it to Conn.
-        if conn_is_none {
-            let mut connect = Connect::new(uri);
-            let c = client.make_connection(&mut connect, &mut timer, req.version()).await?;
-            conn.add(c);
+        let policy = &client.redirect_policy;
+
+        if policy.is_none() {
+            let mut res = send_with_middlewares(req, replay, client, timeout).await?;
+            if decompress {
+                crate::decompress::decompress(&mut res);
+            }
+            return Ok(res);
         }
 
-        let date = client.date_service.handle();
+        let mut method = req.method().clone();
+        let mut uri = req.uri().clone();
+        let version = req.version();
+        let mut headers = req.headers().clone();
 
-        timer
-            .as_mut()
-            .reset(Instant::now() + client.timeout_config.request_timeout);
+        let body = replay.as_ref().and_then(RequestBody::try_clone);
+        let mut res = send_with_middlewares(req, body, client, timeout).await?;
+        let mut history = Vec::new();
 
-        let res = match *conn {
-            #[cfg(feature = "http1")]
-            Connection::Tcp(ref mut stream) => {
-                if matches!(req.version(), Version::HTTP_2 | Version::HTTP_3) {
-                    *req.version_mut() = Version::HTTP_11
-                }
-                crate::h1::proto::send(stream, date, req).timeout(timer.as_mut()).await
+        while let Some(next) = redirect::location(res.status(), res.headers(), &uri) {
+            let status = res.status();
+
+            let attempt = Attempt {
+                status,
+                uri: &uri,
+                next: &next,
+                previous: &history,
+            };
+
+            if !policy.follow(&attempt) {
+                break;
             }
-            #[cfg(feature = "http1")]
-            Connection::Tls(ref mut stream) => {
-                if matches!(req.version(), Version::HTTP_2 | Version::HTTP_3) {
-                    *req.version_mut() = Version::HTTP_11
+
+            match status {
+                StatusCode::SEE_OTHER if method != Method::HEAD => {
+                    method = Method::GET;
+                    replay = Some(RequestBody::None);
                 }
-                crate::h1::proto::send(stream, date, req).timeout(timer.as_mut()).await
-            }
-            #[cfg(feature = "http1")]
-            #[cfg(unix)]
-            Connection::Unix(ref mut stream) => crate::h1::proto::send(stream, date, req).timeout(timer.as_mut()).await,
-            #[cfg(feature = "http2")]
-            Connection::H2(ref mut stream) => {
-                *req.version_mut() = Version::HTTP_2;
-
-                return match crate::h2::proto::send(stream, date, req).timeout(timer.as_mut()).await {
-                    Ok(Ok(res)) => {
-                        let timeout = client.timeout_config.response_timeout;
-                        Ok(Response::new(res, timer, timeout))
-                    }
-                    Ok(Err(e)) => {
-                        conn.destroy_on_drop();
-                        Err(e.into())
-                    }
-                    Err(_) => {
-                        conn.destroy_on_drop();
-                        Err(TimeoutError::Request.into())
-                    }
-                };
+                StatusCode::MOVED_PERMANENTLY | StatusCode::FOUND if method == Method::POST => {
+                    method = Method::GET;
+                    replay = Some(RequestBody::None);
+                }
+                _ => {}
             }
-            #[cfg(feature = "http3")]
-            Connection::H3(ref mut c) => {
-                *req.version_mut() = Version::HTTP_3;
-
-                return match crate::h3::proto::send(c, date, req).timeout(timer.as_mut()).await {
-                    Ok(Ok(res)) => {
-                        let timeout = client.timeout_config.response_timeout;
-                        Ok(Response::new(res, timer, timeout))
-                    }
-                    Ok(Err(e)) => {
-                        conn.destroy_on_drop();
-                        Err(e.into())
-                    }
-                    Err(_) => {
-                        conn.destroy_on_drop();
-                        Err(TimeoutError::Request.into())
-                    }
-                };
+
+            let body = match replay {
+                Some(ref body) => body.try_clone().unwrap(),
+                // streaming body is already consumed.
+                None => break,
+            };
+
+            if matches!(body, RequestBody::None) {
+                for name in [CONTENT_LENGTH, CONTENT_TYPE, CONTENT_ENCODING, TRANSFER_ENCODING] {
+                    headers.remove(name);
+                }
             }
-            #[cfg(not(feature = "http1"))]
-            _ => panic!("http1 feature is not enabled in Cargo.toml"),
-        };
 
-        #[cfg(feature = "http1")]
-        match res {
-            Ok(Ok((res, buf, decoder, is_close))) => {
-                if is_close {
-                    conn.destroy_on_drop();
+            // credentials are not sent to other origin.
+            if !redirect::is_same_origin(&uri, &next) {
+                for name in [AUTHORIZATION, PROXY_AUTHORIZATION, COOKIE, HOST] {
+                    headers.remove(name);
                 }
+            }
+
+            history.push(core::mem::replace(&mut uri, next));
 
-                let body = crate::h1::body::ResponseBody::new(conn, buf, decoder);
-                let res = res.map(|_| crate::body::ResponseBody::H1(body));
-                let timeout = client.timeout_config.response_timeout;
+            let mut req = http::Request::new(body);
+            *req.method_mut() = method.clone();
+            *req.uri_mut() = uri.clone();
+            *req.version_mut() = version;
+            *req.headers_mut() = headers.clone();
 
-                Ok(Response::new(res, timer, timeout))
+            // body of redirect response is not read and connection can't be reused.
+            res.res.body_mut().destroy_on_drop();
+            drop(res);
+
+            let body = req.body().try_clone();
+            res = send_with_middlewares::<_, BodyError>(req, body, client, timeout).await?;
+        }
+
+        res.history = history;
+
+        if decompress {
+            crate::decompress::decompress(&mut res);
+        }
+
+        Ok(res)
+    }
+}
+
+// pass request through middlewares of client before sending it. body is only type erased when
+// there is middleware and replay is the erased body when it's not a stream.
+async fn send_with_middlewares<'a, B, E>(
+    req: http::Request<B>,
+    replay: Option<RequestBody<'a>>,
+    client: &'a Client,
+    timeout: Duration,
+) -> Result<Response<'a>, Error>
+where
+    B: Stream<Item = Result<Bytes, E>>,
+    BodyError: From<E>,
+{
+    if client.middlewares.is_empty() {
+        return send(req, client, timeout).await;
+    }
+
+    let (parts, body) = req.into_parts();
+
+    let (body, feed) = match replay {
+        Some(body) => (body, None),
+        None => {
+            let (body, feed) = RequestBody::pipe(body);
+            (body, Some(feed))
+        }
+    };
+
+    let req = http::Request::from_parts(parts, body);
+    let mut res = pin!(ServiceRequest::new(req, client, timeout).next());
+
+    let Some(feed) = feed else { return res.await };
+
+    let mut feed = pin!(feed);
+    let mut fed = false;
+
+    poll_fn(|cx| {
+        if !fed {
+            fed = feed.as_mut().poll(cx).is_ready();
+        }
+        res.as_mut().poll(cx)
+    })
+    .await
+}
+
+// send request with cookies from cookie store of client and record url and cookies of response.
+#[cfg_attr(not(feature = "cookie"), allow(unused_mut))]
+pub(crate) async fn send<'a, B, E>(
+    mut req: http::Request<B>,
+    client: &'a Client,
+    timeout: Duration,
+) -> Result<Response<'a>, Error>
+where
+    B: Stream<Item = Result<Bytes, E>>,
+    BodyError: From<E>,
+{
+    let url = req.uri().clone();
+
+    #[cfg(feature = "cookie")]
+    if let Some(jar) = client.cookie_store.as_deref() {
+        jar.attach_request(&url, req.headers_mut());
+    }
+
+    let mut res = dispatch(req, client, timeout).await?;
+
+    #[cfg(feature = "cookie")]
+    if let Some(jar) = client.cookie_store.as_deref() {
+        jar.store_response(&url, res.headers());
+    }
+
+    res.url = url;
+
+    Ok(res)
+}
+
+#[allow(unused_variables, unused_mut)]
+async fn dispatch<'a, B, E>(
+    mut req: http::Request<B>,
+    client: &'a Client,
+    timeout: Duration,
+) -> Result<Response<'a>, Error>
+where
+    B: Stream<Item = Result<Bytes, E>>,
+    BodyError: From<E>,
+{
+    let uri = Uri::try_parse(req.uri())?;
+
+    let proxy = client.proxy_for(&uri);
+    let is_forward = proxy.map_or(false, |proxy| proxy.is_forward(&uri));
+
+    // Try to grab a connection from pool.
+    let mut conn = client.pool.acquire(ConnectionKey::new(&uri, proxy)).await?;
+
+    let conn_is_none = conn.is_none();
+
+    // setup timer according to outcome and timeout configs.
+    let dur = if conn_is_none {
+        client.timeout_config.resolve_timeout
+    } else {
+        timeout
+    };
+
+    // heap allocate timer so it can be moved to Response type afterwards
+    let mut timer = Box::pin(tokio::time::sleep(dur));
+
+    // Nothing in the pool. construct new connection and add it to Conn.
+    if conn_is_none {
+        let mut connect = Connect::new(uri);
+        let c = client
+            .make_connection(&mut connect, &mut timer, req.version(), proxy)
+            .await?;
+        conn.add(c);
+    }
+
+    // plain http request to http proxy is sent in absolute form with proxy credentials.
+    if is_forward && !req.headers().contains_key(PROXY_AUTHORIZATION) {
+        if let Some(auth) = proxy.and_then(Proxy::authorization) {
+            req.headers_mut().insert(PROXY_AUTHORIZATION, auth.clone());
+        }
+    }
+
+    let date = client.date_service.handle();
+
+    timer
+        .as_mut()
+        .reset(Instant::now() + client.timeout_config.request_timeout);
+
+    let res = match *conn {
+        #[cfg(feature = "http1")]
+        Connection::Tcp(ref mut stream) => {
+            if matches!(req.version(), Version::HTTP_2 | Version::HTTP_3) {
+                *req.version_mut() = Version::HTTP_11
             }
-            Ok(Err(e)) => {
-                conn.destroy_on_drop();
-                Err(e.into())
+            crate::h1::proto::send(stream, date, req, is_forward)
+                .timeout(timer.as_mut())
+                .await
+        }
+        #[cfg(feature = "http1")]
+        Connection::Tls(ref mut stream) => {
+            if matches!(req.version(), Version::HTTP_2 | Version::HTTP_3) {
+                *req.version_mut() = Version::HTTP_11
             }
-            Err(_) => {
+            crate::h1::proto::send(stream, date, req, false)
+                .timeout(timer.as_mut())
+                .await
+        }
+        #[cfg(feature = "http1")]
+        #[cfg(unix)]
+        Connection::Unix(ref mut stream) => {
+            crate::h1::proto::send(stream, date, req, false)
+                .timeout(timer.as_mut())
+                .await
+        }
+        #[cfg(feature = "http2")]
+        Connection::H2(ref mut stream) => {
+            *req.version_mut() = Version::HTTP_2;
+
+            return match crate::h2::proto::send(stream, date, req).timeout(timer.as_mut()).await {
+                Ok(Ok(res)) => {
+                    let timeout = client.timeout_config.response_timeout;
+                    Ok(Response::new(res, timer, timeout))
+                }
+                Ok(Err(e)) => {
+                    conn.destroy_on_drop();
+                    Err(e.into())
+                }
+                Err(_) => {
+                    conn.destroy_on_drop();
+                    Err(TimeoutError::Request.into())
+                }
+            };
+        }
+        #[cfg(feature = "http3")]
+        Connection::H3(ref mut c) => {
+            *req.version_mut() = Version::HTTP_3;
+
+            return match crate::h3::proto::send(c, date, req).timeout(timer.as_mut()).await {
+                Ok(Ok(res)) => {
+                    let timeout = client.timeout_config.response_timeout;
+                    Ok(Response::new(res, timer, timeout))
+                }
+                Ok(Err(e)) => {
+                    conn.destroy_on_drop();
+                    Err(e.into())
+                }
+                Err(_) => {
+                    conn.destroy_on_drop();
+                    Err(TimeoutError::Request.into())
+                }
+            };
+        }
+        #[cfg(not(feature = "http1"))]
+        _ => panic!("http1 feature is not enabled in Cargo.toml"),
+    };
+
+    #[cfg(feature = "http1")]
+    match res {
+        Ok(Ok((res, buf, decoder, is_close))) => {
+            if is_close {
                 conn.destroy_on_drop();
-                Err(TimeoutError::Request.into())
             }
+
+            let body = crate::h1::body::ResponseBody::new(conn, buf, decoder);
+            let res = res.map(|_| crate::body::ResponseBody::H1(body));
+            let timeout = client.timeout_config.response_timeout;
+
+            Ok(Response::new(res, timer, timeout))
+        }
+        Ok(Err(e)) => {
+            conn.destroy_on_drop();
+            Err(e.into())
+        }
+        Err(_) => {
+            conn.destroy_on_drop();
+            Err(TimeoutError::Request.into())
         }
     }
 }
@@ -8,7 +8,10 @@ use core::{
 use futures_util::StreamExt;
 use tokio::time::{Instant, Sleep};
 use tracing::debug;
-use xitca_http::{bytes::BytesMut, http};
+use xitca_http::{
+    bytes::BytesMut,
+    http::{self, Uri},
+};
 
 use crate::{
     body::ResponseBody,
@@ -22,6 +25,8 @@ pub struct Response<'a, const PAYLOAD_LIMIT: usize = DEFAULT_PAYLOAD_LIMIT> {
     pub(crate) res: http::Response<ResponseBody<'a>>,
     timer: Pin<Box<Sleep>>,
     timeout: Duration,
+    pub(crate) url: Uri,
+    pub(crate) history: Vec<Uri>,
 }
 
 impl<'a, const PAYLOAD_LIMIT: usize> Deref for Response<'a, PAYLOAD_LIMIT> {
@@ -47,7 +52,13 @@ impl<const PAYLOAD_LIMIT: usize> fmt::Debug for Response<'_, PAYLOAD_LIMIT> {
 impl<'a, const PAYLOAD_LIMIT: usize> Response<'a, PAYLOAD_LIMIT> {
     #[cfg(any(feature = "http1", feature = "http2", feature = "http3"))]
     pub(crate) fn new(res: http::Response<ResponseBody<'a>>, timer: Pin<Box<Sleep>>, timeout: Duration) -> Self {
-        Self { res, timer, timeout }
+        Self {
+            res,
+            timer,
+            timeout,
+            url: Uri::default(),
+            history: Vec::new(),
+        }
     }
 
     /// Get a reference of the inner response type.
@@ -60,6 +71,20 @@ impl<'a, const PAYLOAD_LIMIT: usize> Response<'a, PAYLOAD_LIMIT> {
         &mut self.res
     }
 
+    /// URL of the request the response is received from. It's the URL of last redirect when redirects
+    /// are followed.
+    #[inline]
+    pub fn url(&self) -> &Uri {
+        &self.url
+    }
+
+    /// URLs of the requests that are redirected before receiving the response, in the order they are
+    /// sent.
+    #[inline]
+    pub fn history(&self) -> &[Uri] {
+        &self.history
+    }
+
     /// Set payload size limit in bytes. Payload size beyond limit would be discarded.
     ///
     /// Default to 8 Mb.
@@ -69,6 +94,8 @@ impl<'a, const PAYLOAD_LIMIT: usize> Response<'a, PAYLOAD_LIMIT> {
             res: self.res,
             timer: self.timer,
             timeout: self.timeout,
+            url: self.url,
+            history: self.history,
         }
     }
 
@@ -82,6 +109,8 @@ impl<'a, const PAYLOAD_LIMIT: usize> Response<'a, PAYLOAD_LIMIT> {
             res: self.res,
             timer: self.timer,
             timeout: dur,
+            url: self.url,
+            history: self.history,
         }
     }
 
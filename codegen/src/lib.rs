@@ -1,7 +1,8 @@
 use proc_macro::TokenStream;
 use quote::{__private::Span, quote};
 use syn::{
-    Data, FnArg, GenericArgument, Ident, ImplItem, ImplItemMethod, Pat, PatIdent, PathArguments, ReturnType, Stmt, Type,
+    Data, Fields, FnArg, GenericArgument, GenericParam, Ident, ImplItem, ImplItemMethod, Lifetime, LifetimeDef, Lit,
+    Meta, Pat, PatIdent, PathArguments, ReturnType, Stmt, Type,
 };
 
 #[proc_macro_derive(State, attributes(borrow))]
@@ -44,6 +45,78 @@ pub fn state_impl(item: TokenStream) -> TokenStream {
     quote! { #(#fields)* }.into()
 }
 
+#[proc_macro_derive(FromRow, attributes(rename, flatten))]
+pub fn from_row_impl(item: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(item as syn::DeriveInput);
+
+    let ty_ident = &input.ident;
+    let fields = match input.data {
+        Data::Struct(ref ty) => match ty.fields {
+            Fields::Named(ref fields) => &fields.named,
+            _ => panic!("FromRow can only be derived for struct with named fields"),
+        },
+        _ => panic!("FromRow can only be derived for struct"),
+    };
+
+    // use the first lifetime of struct as row lifetime so fields can borrow from row.
+    let mut generics = input.generics.clone();
+    let lifetime = match generics.lifetimes().next() {
+        Some(def) => def.lifetime.clone(),
+        None => {
+            let lifetime = Lifetime::new("'__r", Span::call_site());
+            generics
+                .params
+                .insert(0, GenericParam::Lifetime(LifetimeDef::new(lifetime.clone())));
+            lifetime
+        }
+    };
+    let (impl_generics, _, _) = generics.split_for_impl();
+    let (_, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let fields = fields.iter().map(|field| {
+        let ident = field.ident.as_ref().unwrap();
+        let ty = &field.ty;
+
+        let mut name = ident.to_string();
+        let mut flatten = false;
+
+        for attr in field.attrs.iter() {
+            if attr.path.is_ident("flatten") {
+                flatten = true;
+            } else if attr.path.is_ident("rename") {
+                match attr.parse_meta() {
+                    Ok(Meta::NameValue(meta)) => match meta.lit {
+                        Lit::Str(lit) => name = lit.value(),
+                        _ => panic!("rename attribute must be a string literal. e.g: #[rename = \"name\"]"),
+                    },
+                    _ => panic!("rename attribute must be in form of #[rename = \"name\"]"),
+                }
+            }
+        }
+
+        if flatten {
+            quote! {
+                #ident: <#ty as ::xitca_postgres::FromRow<#lifetime>>::from_row(row)?
+            }
+        } else {
+            quote! {
+                #ident: row.try_get::<#ty>(#name)?
+            }
+        }
+    });
+
+    quote! {
+        impl #impl_generics ::xitca_postgres::FromRow<#lifetime> for #ty_ident #ty_generics #where_clause {
+            fn from_row(row: &#lifetime ::xitca_postgres::Row<#lifetime>) -> Result<Self, ::xitca_postgres::error::Error> {
+                Ok(Self {
+                    #(#fields),*
+                })
+            }
+        }
+    }
+    .into()
+}
+
 #[proc_macro_attribute]
 pub fn service_impl(_attr: TokenStream, item: TokenStream) -> TokenStream {
     middleware_impl(_attr, item)
@@ -31,6 +31,18 @@ where
     pub fn new(body: S, coder: C) -> Self {
         Self { body, coder }
     }
+
+    /// Get a reference of the inner stream.
+    #[inline]
+    pub fn get_ref(&self) -> &S {
+        &self.body
+    }
+
+    /// Get a mutable reference of the inner stream.
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut S {
+        &mut self.body
+    }
 }
 
 impl<S, C, T, E> Stream for Coder<S, C>
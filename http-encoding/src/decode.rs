@@ -17,7 +17,17 @@ where
     S: Stream<Item = Result<T, E>>,
     T: AsRef<[u8]> + 'static,
 {
-    from_headers(req.borrow().headers()).map(|decoder| Coder::new(body, decoder))
+    try_decoder_from_headers(req.borrow().headers(), body)
+}
+
+/// Construct from headers and stream body. Use for decoding response of client.
+#[inline]
+pub fn try_decoder_from_headers<S, T, E>(headers: &HeaderMap, body: S) -> Result<Coder<S, FeaturedCode>, EncodingError>
+where
+    S: Stream<Item = Result<T, E>>,
+    T: AsRef<[u8]> + 'static,
+{
+    from_headers(headers).map(|decoder| Coder::new(body, decoder))
 }
 
 fn from_headers(headers: &HeaderMap) -> Result<FeaturedCode, EncodingError> {
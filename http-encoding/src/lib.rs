@@ -116,5 +116,5 @@ mod deflate {
 
 pub use self::coder::{Code, Coder, FeaturedCode};
 pub use self::coding::ContentEncoding;
-pub use self::decode::try_decoder;
+pub use self::decode::{try_decoder, try_decoder_from_headers};
 pub use self::encode::encoder;
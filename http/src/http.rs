@@ -9,6 +9,9 @@ use core::{
 
 use std::net::SocketAddr;
 
+#[cfg(feature = "util-service")]
+use std::sync::Arc;
+
 use futures_core::stream::Stream;
 use pin_project_lite::pin_project;
 
@@ -29,6 +32,7 @@ pub mod const_header_value {
         (TEXT, "text/plain"),
         (TEXT_UTF8, "text/plain; charset=utf-8"),
         (JSON, "application/json"),
+        (FORM_URLENCODED, "application/x-www-form-urlencoded"),
         (TEXT_HTML_UTF8, "text/html; charset=utf-8"),
         (GRPC, "application/grpc"),
         (WEBSOCKET, "websocket")
@@ -88,7 +92,7 @@ where
 }
 
 #[cfg(feature = "util-service")]
-use super::util::service::router::Params;
+use super::util::service::router::{NestedPrefix, Params, Urls};
 
 pin_project! {
     /// typed http extension
@@ -111,6 +115,10 @@ impl Extension {
             addr,
             #[cfg(feature = "util-service")]
             params: Default::default(),
+            #[cfg(feature = "util-service")]
+            nested_prefix: Default::default(),
+            #[cfg(feature = "util-service")]
+            urls: None,
         }))
     }
 }
@@ -120,6 +128,10 @@ struct _Extension {
     addr: SocketAddr,
     #[cfg(feature = "util-service")]
     params: Params,
+    #[cfg(feature = "util-service")]
+    nested_prefix: NestedPrefix,
+    #[cfg(feature = "util-service")]
+    urls: Option<Arc<Urls>>,
 }
 
 impl<B> RequestExt<B> {
@@ -167,6 +179,16 @@ impl<B> RequestExt<B> {
     pub fn params_mut(&mut self) -> &mut Params {
         &mut self.ext.0.params
     }
+
+    #[inline]
+    pub fn nested_prefix(&self) -> NestedPrefix {
+        self.ext.0.nested_prefix
+    }
+
+    #[inline]
+    pub fn urls(&self) -> Option<&Urls> {
+        self.ext.0.urls.as_deref()
+    }
 }
 
 impl<B> Default for RequestExt<B>
@@ -218,6 +240,38 @@ impl<B> BorrowMut<Params> for RequestExt<B> {
     }
 }
 
+#[cfg(feature = "util-service")]
+impl<B> Borrow<NestedPrefix> for RequestExt<B> {
+    #[inline]
+    fn borrow(&self) -> &NestedPrefix {
+        &self.ext.0.nested_prefix
+    }
+}
+
+#[cfg(feature = "util-service")]
+impl<B> BorrowMut<NestedPrefix> for RequestExt<B> {
+    #[inline]
+    fn borrow_mut(&mut self) -> &mut NestedPrefix {
+        &mut self.ext.0.nested_prefix
+    }
+}
+
+#[cfg(feature = "util-service")]
+impl<B> Borrow<Option<Arc<Urls>>> for RequestExt<B> {
+    #[inline]
+    fn borrow(&self) -> &Option<Arc<Urls>> {
+        &self.ext.0.urls
+    }
+}
+
+#[cfg(feature = "util-service")]
+impl<B> BorrowMut<Option<Arc<Urls>>> for RequestExt<B> {
+    #[inline]
+    fn borrow_mut(&mut self) -> &mut Option<Arc<Urls>> {
+        &mut self.ext.0.urls
+    }
+}
+
 /// trait for Borrow &T from &Self.
 /// used for foreign types (from xitca-http pov) that can be impl with [Borrow] trait.
 pub trait BorrowReq<T> {
@@ -244,6 +298,13 @@ impl<Ext> BorrowReq<Method> for Request<Ext> {
     }
 }
 
+impl<Ext> BorrowReq<HeaderMap> for Request<Ext> {
+    #[inline]
+    fn borrow(&self) -> &HeaderMap {
+        self.headers()
+    }
+}
+
 impl<Ext> BorrowReqMut<Extensions> for Request<Ext> {
     #[inline]
     fn borrow_mut(&mut self) -> &mut Extensions {
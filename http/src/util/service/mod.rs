@@ -9,7 +9,11 @@ pub mod context {
 }
 
 pub mod router {
-    pub use super::router_priv::{GenericRouter, MatchError, Params, Router, RouterError};
+    pub use super::router_priv::{
+        EnclosedRoute, FeatureOr, Features, GenericRouter, HostFeature, HostRouter, InsertError, IntoRoutes, MatchError,
+        NestFeature, NestedPrefix, Params, RouteEntry, Router, RouterError, RouterFeature, RouterReload, UrlError, Urls,
+        UrlsFeature,
+    };
 }
 
 pub use router_priv::{GenericRouter, Router, RouterError};
@@ -1,17 +1,18 @@
-pub use xitca_router::{params::Params, MatchError};
+pub use xitca_router::{params::Params, HostRouter, InsertError, MatchError, UrlError, Urls};
 
 use core::{future::Future, marker::PhantomData};
 
-use std::collections::HashMap;
+use std::{collections::HashMap, sync::Arc};
 
+use arc_swap::ArcSwapOption;
 use xitca_service::{
     object::{DefaultObject, DefaultObjectConstructor, ObjectConstructor},
-    pipeline::PipelineE,
+    pipeline::{EnclosedFactory, PipelineE},
     ready::ReadyService,
     Service,
 };
 
-use crate::http::{BorrowReq, BorrowReqMut, Uri};
+use crate::http::{header::HOST, BorrowReq, BorrowReqMut, HeaderMap, Uri};
 
 /// A [GenericRouter] specialized with [DefaultObjectConstructor]
 pub type Router<Req, Arg, BErr, Res, Err> =
@@ -21,9 +22,16 @@ pub type Router<Req, Arg, BErr, Res, Err> =
 ///
 /// An [ObjectConstructor] must be specified as a type prameter
 /// in order to determine how the router type-erases node services.
-pub struct GenericRouter<ObjCons, SF> {
-    routes: HashMap<&'static str, SF>,
+///
+/// Routing features enabled by [GenericRouter::nest], [GenericRouter::with_urls] and [GenericRouter::host] are tracked
+/// with [Features] type parameter and add their bounds to request type of router service only when
+/// enabled.
+pub struct GenericRouter<ObjCons, SF, F = Features> {
+    routes: Vec<(String, RouteEntry<SF>)>,
+    reload: Option<RouterReload>,
+    urls: bool,
     _req_body: PhantomData<ObjCons>,
+    _features: PhantomData<F>,
 }
 
 /// Error type of Router service.
@@ -52,31 +60,499 @@ impl<SF> GenericRouter<(), SF> {
 impl<ObjCons, SF> GenericRouter<ObjCons, SF> {
     pub fn new() -> Self {
         Self {
-            routes: HashMap::new(),
+            routes: Vec::new(),
+            reload: None,
+            urls: false,
             _req_body: PhantomData,
+            _features: PhantomData,
         }
     }
+}
 
+impl<ObjCons, SF, N, U, H> GenericRouter<ObjCons, SF, Features<N, U, H>> {
     /// Insert a new service factory to given path.
     ///
     /// # Panic:
     ///
     /// When multiple services inserted with the same path.
-    pub fn insert<F>(mut self, path: &'static str, factory: F) -> Self
+    pub fn insert<F>(self, path: &'static str, factory: F) -> Self
+    where
+        ObjCons: ObjectConstructor<F, Object = SF>,
+    {
+        self.insert_entry(path.to_owned(), RouteEntry::new(None, factory))
+    }
+
+    /// Insert a new service factory to given path with a name. The name can be used to generate URL
+    /// path of the route with [Urls] which can be obtained from [RequestExt::urls](crate::http::RequestExt::urls)
+    /// when router is enabled with [GenericRouter::with_urls].
+    ///
+    /// # Panic:
+    ///
+    /// When multiple services inserted with the same path or name.
+    pub fn insert_named<F>(self, name: &'static str, path: &'static str, factory: F) -> Self
+    where
+        ObjCons: ObjectConstructor<F, Object = SF>,
+    {
+        self.insert_entry(path.to_owned(), RouteEntry::new(Some(name), factory))
+    }
+
+    /// Store [Urls] of named routes in request with `BorrowReqMut<Option<Arc<Urls>>>`.
+    ///
+    /// It's ignored when the router is nested into other router or has no named route.
+    pub fn with_urls(mut self) -> GenericRouter<ObjCons, SF, Features<N, UrlsFeature, H>> {
+        self.urls = true;
+        self.with_features()
+    }
+
+    /// Enable hot reloading route table of router with given [RouterReload] handle.
+    ///
+    /// The handle is ignored when the router is nested into other router.
+    ///
+    /// # Panic:
+    ///
+    /// When router has routes inserted with [GenericRouter::host].
+    pub fn with_reload(mut self, handle: RouterReload) -> Self {
+        assert!(
+            self.routes.iter().all(|(_, e)| e.host.is_none()),
+            "hot reload can not be enabled for router with host routes"
+        );
+        self.reload = Some(handle);
+        self
+    }
+
+    /// Insert all routes of given [IntoRoutes] type under given path prefix.
+    ///
+    /// Routes are flattened into current router and params of prefix are merged into the params of
+    /// nested routes. [NestedPrefix] of matched route is stored in request with
+    /// `BorrowReqMut<NestedPrefix>` and the path with prefix stripped can be obtained from it.
+    ///
+    /// # Examples:
+    /// ```rust
+    /// # use std::convert::Infallible;
+    /// # use xitca_http::{http::{Request, RequestExt, Response}, util::service::Router};
+    /// # use xitca_service::fn_service;
+    /// let api = Router::new().insert(
+    ///     "/users/:id",
+    ///     fn_service(|_: Request<RequestExt<()>>| async { Ok::<_, Infallible>(Response::new(())) }),
+    /// );
+    ///
+    /// // route of "/users/:id" is reachable from "/api/:version/users/:id".
+    /// let router = Router::new().nest("/api/:version", api);
+    /// ```
+    ///
+    /// # Panic:
+    ///
+    /// When prefix does not start with `/` or contains catch-all parameter.
+    /// When multiple services inserted with the same path.
+    #[allow(clippy::type_complexity)]
+    pub fn nest<R>(
+        mut self,
+        prefix: &'static str,
+        routes: R,
+    ) -> GenericRouter<ObjCons, SF, Features<NestFeature, U, <H as FeatureOr<R::Host>>::Output>>
+    where
+        R: IntoRoutes,
+        H: FeatureOr<R::Host>,
+        ObjCons: ObjectConstructor<R::Route, Object = SF>,
+    {
+        assert!(prefix.starts_with('/'), "nested prefix must start with /");
+        assert!(!prefix.contains('*'), "catch-all param in nested prefix");
+
+        let prefix = prefix.trim_end_matches('/');
+        let segments = prefix.matches('/').count();
+
+        for (path, entry) in routes.into_routes() {
+            let path = format!("{prefix}{path}");
+            let entry = RouteEntry {
+                nested: segments + entry.nested,
+                ..entry
+            };
+            self = self.insert_entry(path, entry);
+        }
+
+        self.with_features()
+    }
+
+    /// Insert all routes of given [IntoRoutes] type under given host pattern.
+    ///
+    /// Host of request is obtained from it's authority or `Host` header and routes of matched host
+    /// are tried before routes without host. Captured host params are merged into the params of
+    /// routes. See [HostRouter] for host pattern syntax and matching priority.
+    ///
+    /// # Examples:
+    /// ```rust
+    /// # use std::convert::Infallible;
+    /// # use xitca_http::{http::{Request, RequestExt, Response}, util::service::Router};
+    /// # use xitca_service::fn_service;
+    /// let tenant = Router::new().insert(
+    ///     "/users/:id",
+    ///     fn_service(|req: Request<RequestExt<()>>| async move {
+    ///         let _tenant = req.body().params().get("tenant");
+    ///         Ok::<_, Infallible>(Response::new(()))
+    ///     }),
+    /// );
+    ///
+    /// // route of "/users/:id" is reachable from "foo.example.com/users/:id" with "foo" as tenant.
+    /// let router = Router::new().host("{tenant}.example.com", tenant);
+    /// ```
+    ///
+    /// # Panic:
+    ///
+    /// When host pattern is invalid or given routes already have host.
+    /// When multiple services inserted with the same host and path.
+    /// When hot reload is enabled for router with [GenericRouter::with_reload].
+    #[allow(clippy::type_complexity)]
+    pub fn host<R>(
+        mut self,
+        host: &'static str,
+        routes: R,
+    ) -> GenericRouter<ObjCons, SF, Features<<N as FeatureOr<R::Nest>>::Output, U, HostFeature>>
+    where
+        R: IntoRoutes,
+        N: FeatureOr<R::Nest>,
+        ObjCons: ObjectConstructor<R::Route, Object = SF>,
+    {
+        if let Err(e) = HostRouter::new().insert(host, ()) {
+            panic!("invalid host pattern: {e}");
+        }
+
+        for (path, entry) in routes.into_routes() {
+            assert!(entry.host.is_none(), "routes of host: {host} already have host");
+            let entry = RouteEntry {
+                host: Some(host),
+                ..entry
+            };
+            self = self.insert_entry(path, entry);
+        }
+
+        self.with_features()
+    }
+
+    fn insert_entry<F>(mut self, path: String, entry: RouteEntry<F>) -> Self
     where
         ObjCons: ObjectConstructor<F, Object = SF>,
     {
-        assert!(self.routes.insert(path, ObjCons::into_object(factory)).is_none());
+        assert!(
+            entry.host.is_none() || self.reload.is_none(),
+            "host routes can not be inserted into router with hot reload enabled"
+        );
+        if let Some(name) = entry.name {
+            assert!(
+                self.routes.iter().all(|(_, e)| e.name != Some(name)),
+                "route name: {name} is already registered"
+            );
+        }
+        assert!(
+            self.routes.iter().all(|(p, e)| *p != path || e.host != entry.host),
+            "route path: {path} is already registered"
+        );
+        self.routes.push((path, entry.map(ObjCons::into_object)));
         self
     }
+
+    fn with_features<F>(self) -> GenericRouter<ObjCons, SF, F> {
+        GenericRouter {
+            routes: self.routes,
+            reload: self.reload,
+            urls: self.urls,
+            _req_body: PhantomData,
+            _features: PhantomData,
+        }
+    }
+}
+
+/// Type level set of routing features enabled for [GenericRouter]. A disabled feature is `()`.
+///
+/// Request type of router service is required to be `BorrowReq<Uri> + BorrowReqMut<Params>` and each
+/// enabled feature adds its own bound:
+/// - `BorrowReqMut<NestedPrefix>` for [NestFeature]
+/// - `BorrowReqMut<Option<Arc<Urls>>>` for [UrlsFeature]
+/// - `BorrowReq<HeaderMap>` for [HostFeature]
+pub struct Features<N = (), U = (), H = ()>(PhantomData<(N, U, H)>);
+
+/// Feature enabled by [GenericRouter::nest].
+pub struct NestFeature;
+
+/// Feature enabled by [GenericRouter::with_urls].
+pub struct UrlsFeature;
+
+/// Feature enabled by [GenericRouter::host].
+pub struct HostFeature;
+
+/// Trait for reading and storing routing info of request when according feature is enabled.
+pub trait RouterFeature<Req> {
+    fn host(_: &Req) -> Option<&str> {
+        None
+    }
+
+    fn set_nested_prefix(_: &mut Req, _: NestedPrefix) {}
+
+    fn set_urls(_: &mut Req, _: &Arc<Urls>) {}
+}
+
+impl<Req> RouterFeature<Req> for () {}
+
+impl<Req> RouterFeature<Req> for NestFeature
+where
+    Req: BorrowReqMut<NestedPrefix>,
+{
+    #[inline]
+    fn set_nested_prefix(req: &mut Req, prefix: NestedPrefix) {
+        *req.borrow_mut() = prefix;
+    }
+}
+
+impl<Req> RouterFeature<Req> for UrlsFeature
+where
+    Req: BorrowReqMut<Option<Arc<Urls>>>,
+{
+    #[inline]
+    fn set_urls(req: &mut Req, urls: &Arc<Urls>) {
+        *req.borrow_mut() = Some(urls.clone());
+    }
+}
+
+impl<Req> RouterFeature<Req> for HostFeature
+where
+    Req: BorrowReq<Uri> + BorrowReq<HeaderMap>,
+{
+    #[inline]
+    fn host(req: &Req) -> Option<&str> {
+        BorrowReq::<Uri>::borrow(req)
+            .host()
+            .or_else(|| BorrowReq::<HeaderMap>::borrow(req).get(HOST)?.to_str().ok())
+    }
+}
+
+/// Type level or of routing features. Used for merging features of nested routes.
+pub trait FeatureOr<Rhs> {
+    type Output;
+}
+
+impl<T> FeatureOr<T> for () {
+    type Output = T;
+}
+
+impl<T> FeatureOr<T> for NestFeature {
+    type Output = NestFeature;
+}
+
+impl<T> FeatureOr<T> for HostFeature {
+    type Output = HostFeature;
+}
+
+/// A route of [IntoRoutes] type.
+pub struct RouteEntry<F> {
+    name: Option<&'static str>,
+    host: Option<&'static str>,
+    // number of path segments belong to nested prefix of route path.
+    nested: usize,
+    factory: F,
+}
+
+impl<F> RouteEntry<F> {
+    fn new(name: Option<&'static str>, factory: F) -> Self {
+        Self {
+            name,
+            host: None,
+            nested: 0,
+            factory,
+        }
+    }
+
+    fn map<F1>(self, func: impl FnOnce(F) -> F1) -> RouteEntry<F1> {
+        RouteEntry {
+            name: self.name,
+            host: self.host,
+            nested: self.nested,
+            factory: func(self.factory),
+        }
+    }
+}
+
+/// Trait for types that are made of routes and can be nested into [GenericRouter] with
+/// [GenericRouter::nest].
+pub trait IntoRoutes {
+    /// Service factory type of each route.
+    type Route;
+
+    /// Nesting feature required by the routes. See [Features].
+    type Nest;
+
+    /// Host feature required by the routes. See [Features].
+    type Host;
+
+    /// Destruct self into a list of routes paired with their paths.
+    fn into_routes(self) -> Vec<(String, RouteEntry<Self::Route>)>;
+}
+
+impl<ObjCons, SF, N, U, H> IntoRoutes for GenericRouter<ObjCons, SF, Features<N, U, H>> {
+    type Route = SF;
+    type Nest = N;
+    type Host = H;
+
+    fn into_routes(self) -> Vec<(String, RouteEntry<Self::Route>)> {
+        self.routes
+    }
+}
+
+// middleware enclosing a router is applied to every route of it when nested.
+impl<R, T> IntoRoutes for EnclosedFactory<R, T>
+where
+    R: IntoRoutes,
+    T: Clone,
+{
+    type Route = EnclosedRoute<R::Route, T>;
+    type Nest = R::Nest;
+    type Host = R::Host;
+
+    fn into_routes(self) -> Vec<(String, RouteEntry<Self::Route>)> {
+        let transform = self.second;
+        self.first
+            .into_routes()
+            .into_iter()
+            .map(|(path, entry)| {
+                let entry = entry.map(|route| EnclosedRoute {
+                    route,
+                    transform: transform.clone(),
+                });
+                (path, entry)
+            })
+            .collect()
+    }
+}
+
+/// A route of nested router enclosed by middleware.
+///
+/// Unlike [EnclosedFactory] the error of middleware is converted into the error of route so nested
+/// routes share the same error type with other routes.
+pub struct EnclosedRoute<R, T> {
+    route: R,
+    transform: T,
 }
 
-impl<ObjCons, SF, Arg> Service<Arg> for GenericRouter<ObjCons, SF>
+impl<R, T, Arg> Service<Arg> for EnclosedRoute<R, T>
+where
+    R: Service<Arg>,
+    T: Service<R::Response>,
+    R::Error: From<T::Error>,
+{
+    type Response = T::Response;
+    type Error = R::Error;
+    type Future<'f> = impl Future<Output = Result<Self::Response, Self::Error>> + 'f where Self: 'f, Arg: 'f;
+
+    fn call<'s>(&'s self, arg: Arg) -> Self::Future<'s>
+    where
+        Arg: 's,
+    {
+        async move {
+            let service = self.route.call(arg).await?;
+            let service = self.transform.call(service).await?;
+            Ok(service)
+        }
+    }
+}
+
+/// Handle for hot reloading route table of [RouterService] without rebuilding the services.
+///
+/// A reloaded route table maps paths to the routes inserted with [GenericRouter::insert_named] by
+/// their names. It replaces the original route table of all [RouterService] built from the
+/// [GenericRouter] enabled with [GenericRouter::with_reload]. The handle can be cloned and shared
+/// between threads.
+///
+/// # Examples:
+/// ```rust
+/// # use std::convert::Infallible;
+/// # use xitca_http::{http::{Request, RequestExt, Response}, util::service::{router::RouterReload, Router}};
+/// # use xitca_service::fn_service;
+/// let reload = RouterReload::new();
+///
+/// let router = Router::new()
+///     .insert_named(
+///         "user",
+///         "/users/:id",
+///         fn_service(|_: Request<RequestExt<()>>| async { Ok::<_, Infallible>(Response::new(())) }),
+///     )
+///     .with_reload(reload.clone());
+///
+/// // after router is built and running "user" route is served on new paths.
+/// reload.reload([("/v2/users/:id", "user"), ("/members/:id", "user")]).unwrap();
+/// ```
+#[derive(Clone, Default)]
+pub struct RouterReload {
+    table: Arc<ArcSwapOption<RouteTable>>,
+}
+
+struct RouteTable {
+    routes: xitca_router::Router<String>,
+    urls: Option<Arc<Urls>>,
+}
+
+impl RouterReload {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replace route table with given routes in the form of (path, route name).
+    ///
+    /// Request to a path mapped to a name that is not registered results in [MatchError::NotFound].
+    /// When multiple paths are mapped to the same name the first one is used for URL generation.
+    /// Routes inserted with [GenericRouter::nest] keep their [NestedPrefix] of the same number of
+    /// path segments on reloaded paths.
+    pub fn reload<I, P, N>(&self, routes: I) -> Result<(), InsertError>
+    where
+        I: IntoIterator<Item = (P, N)>,
+        P: Into<String>,
+        N: Into<String>,
+    {
+        let mut table = xitca_router::Router::new();
+
+        for (path, name) in routes {
+            let name = name.into();
+            if table.urls().route(&name).is_some() {
+                table.insert(path, name)?;
+            } else {
+                table.insert_named(name.clone(), path, name)?;
+            }
+        }
+
+        let urls = (!table.urls().is_empty()).then(|| Arc::new(table.urls().clone()));
+        let table = RouteTable { routes: table, urls };
+
+        self.table.store(Some(Arc::new(table)));
+
+        Ok(())
+    }
+
+    /// Restore the original route table of router.
+    pub fn reset(&self) {
+        self.table.store(None);
+    }
+
+    fn table(&self) -> Option<Arc<RouteTable>> {
+        self.table.load_full()
+    }
+}
+
+/// Byte length of the prefix of request path matched by a nested router.
+///
+/// It's stored in request when a route added by [GenericRouter::nest] is matched and zero otherwise.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct NestedPrefix(pub usize);
+
+impl NestedPrefix {
+    /// Strip the prefix from given path.
+    pub fn strip<'a>(&self, path: &'a str) -> &'a str {
+        path.get(self.0..).unwrap_or(path)
+    }
+}
+
+impl<ObjCons, SF, F, Arg> Service<Arg> for GenericRouter<ObjCons, SF, F>
 where
     SF: Service<Arg>,
     Arg: Clone,
 {
-    type Response = RouterService<SF::Response>;
+    type Response = RouterService<SF::Response, F>;
     type Error = SF::Error;
     type Future<'f> = impl Future<Output = Result<Self::Response, Self::Error>> + 'f where Self: 'f, Arg: 'f;
 
@@ -86,25 +562,75 @@ where
     {
         async move {
             let mut routes = xitca_router::Router::new();
+            let mut host_routes = Vec::<(&str, xitca_router::Router<usize>)>::new();
+            let mut services = Vec::with_capacity(self.routes.len());
+            let mut names = HashMap::new();
+
+            for (path, entry) in self.routes.iter() {
+                let idx = services.len();
+                services.push((entry.nested, entry.factory.call(arg.clone()).await?));
+
+                let routes = match entry.host {
+                    Some(host) => match host_routes.iter().position(|(h, _)| *h == host) {
+                        Some(i) => &mut host_routes[i].1,
+                        None => {
+                            host_routes.push((host, xitca_router::Router::new()));
+                            &mut host_routes.last_mut().unwrap().1
+                        }
+                    },
+                    None => &mut routes,
+                };
 
-            for (path, service) in self.routes.iter() {
-                let service = service.call(arg.clone()).await?;
-                routes.insert(*path, service).unwrap();
+                match entry.name {
+                    Some(name) => {
+                        routes.insert_named(name, path.as_str(), idx).unwrap();
+                        names.insert(name, idx);
+                    }
+                    None => routes.insert(path.as_str(), idx).unwrap(),
+                }
             }
 
-            Ok(RouterService { routes })
+            let mut urls = routes.urls().clone();
+            let mut hosts = HostRouter::new();
+            for (host, routes) in host_routes {
+                urls.merge(routes.urls());
+                hosts.insert(host, routes).unwrap();
+            }
+            let urls = (self.urls && !urls.is_empty()).then(|| Arc::new(urls));
+
+            Ok(RouterService {
+                routes,
+                hosts,
+                services,
+                names,
+                urls,
+                reload: self.reload.clone(),
+                _features: PhantomData,
+            })
         }
     }
 }
 
-pub struct RouterService<S> {
-    routes: xitca_router::Router<S>,
+pub struct RouterService<S, F = Features> {
+    // value is the index of service.
+    routes: xitca_router::Router<usize>,
+    // routes of host patterns. tried before routes without host.
+    hosts: HostRouter<xitca_router::Router<usize>>,
+    // tuple of (number of path segments belong to nested prefix, service).
+    services: Vec<(usize, S)>,
+    names: HashMap<&'static str, usize>,
+    urls: Option<Arc<Urls>>,
+    reload: Option<RouterReload>,
+    _features: PhantomData<F>,
 }
 
-impl<S, Req> Service<Req> for RouterService<S>
+impl<S, N, U, H, Req> Service<Req> for RouterService<S, Features<N, U, H>>
 where
     S: Service<Req>,
     Req: BorrowReq<Uri> + BorrowReqMut<Params>,
+    N: RouterFeature<Req>,
+    U: RouterFeature<Req>,
+    H: RouterFeature<Req>,
 {
     type Response = S::Response;
     type Error = RouterError<S::Error>;
@@ -116,19 +642,85 @@ where
         Req: 's,
     {
         async {
-            let xitca_router::Match { value, params } =
-                self.routes.at(req.borrow().path()).map_err(RouterError::First)?;
+            let path = BorrowReq::<Uri>::borrow(&req).path();
+
+            let table = self.reload.as_ref().and_then(RouterReload::table);
 
-            *req.borrow_mut() = params;
+            let (idx, params, urls) = match table {
+                Some(ref table) => {
+                    let xitca_router::Match { value, params } = table.routes.at(path).map_err(RouterError::First)?;
+                    let idx = *self
+                        .names
+                        .get(value.as_str())
+                        .ok_or(RouterError::First(MatchError::NotFound))?;
+                    // reloaded urls are only inserted when router is enabled with them.
+                    (idx, params, self.urls.as_ref().and(table.urls.as_ref()))
+                }
+                None => {
+                    let xitca_router::Match { value, params } = self.route(&req, path).map_err(RouterError::First)?;
+                    (*value, params, self.urls.as_ref())
+                }
+            };
 
-            value.call(req).await.map_err(RouterError::Second)
+            // nesting depth belongs to the route so reloaded path of it keeps the same prefix length.
+            let prefix = match self.services[idx].0 {
+                0 => None,
+                nested => {
+                    let len = path
+                        .match_indices('/')
+                        .nth(nested)
+                        .map(|(i, _)| i)
+                        .unwrap_or(path.len());
+                    Some(NestedPrefix(len))
+                }
+            };
+
+            if let Some(prefix) = prefix {
+                N::set_nested_prefix(&mut req, prefix);
+            }
+
+            if let Some(urls) = urls {
+                U::set_urls(&mut req, urls);
+            }
+
+            *BorrowReqMut::<Params>::borrow_mut(&mut req) = params;
+
+            self.services[idx].1.call(req).await.map_err(RouterError::Second)
         }
     }
 }
 
-impl<S> ReadyService for RouterService<S> {
+impl<S, N, U, H> RouterService<S, Features<N, U, H>> {
+    fn route<Req>(&self, req: &Req, path: &str) -> Result<xitca_router::Match<&usize>, MatchError>
+    where
+        H: RouterFeature<Req>,
+    {
+        if !self.hosts.is_empty() {
+            let host = H::host(req);
+
+            if let Some(xitca_router::Match {
+                value,
+                params: mut host_params,
+            }) = host.and_then(|host| self.hosts.at(host).ok())
+            {
+                // fall through to routes without host when path is not found.
+                if let Ok(xitca_router::Match { value, params }) = value.at(path) {
+                    host_params.extend(params);
+                    return Ok(xitca_router::Match {
+                        value,
+                        params: host_params,
+                    });
+                }
+            }
+        }
+
+        self.routes.at(path)
+    }
+}
+
+impl<S, F> ReadyService for RouterService<S, F> {
     type Ready = ();
-    type ReadyFuture<'f> = impl Future<Output = Self::Ready> where S: 'f;
+    type ReadyFuture<'f> = impl Future<Output = Self::Ready> where Self: 'f;
 
     #[inline]
     fn ready(&self) -> Self::ReadyFuture<'_> {
@@ -140,7 +732,7 @@ impl<S> ReadyService for RouterService<S> {
 mod test {
     use std::convert::Infallible;
 
-    use xitca_service::{fn_service, Service, ServiceExt};
+    use xitca_service::{fn_service, middleware::UncheckedReady, Service, ServiceExt};
     use xitca_unsafe_collection::futures::NowOrPanic;
 
     use crate::http::{Request, RequestExt, Response};
@@ -213,4 +805,248 @@ mod test {
             .now_or_panic()
             .unwrap();
     }
+
+    #[test]
+    fn router_plain_request() {
+        struct PlainRequest {
+            uri: Uri,
+            params: Params,
+        }
+
+        impl BorrowReq<Uri> for PlainRequest {
+            fn borrow(&self) -> &Uri {
+                &self.uri
+            }
+        }
+
+        impl BorrowReqMut<Params> for PlainRequest {
+            fn borrow_mut(&mut self) -> &mut Params {
+                &mut self.params
+            }
+        }
+
+        let req = PlainRequest {
+            uri: Uri::from_static("/users/1"),
+            params: Params::default(),
+        };
+
+        Router::new()
+            .insert(
+                "/users/:id",
+                fn_service(|req: PlainRequest| async move {
+                    assert_eq!(req.params.get("id").unwrap(), "1");
+                    Ok::<_, Infallible>(Response::new(()))
+                }),
+            )
+            .call(())
+            .now_or_panic()
+            .unwrap()
+            .call(req)
+            .now_or_panic()
+            .unwrap();
+    }
+
+    #[test]
+    fn router_nest() {
+        let api = Router::new()
+            .insert(
+                "/users/:id",
+                fn_service(|req: Request<RequestExt<()>>| async move {
+                    let params = req.body().params();
+                    assert_eq!(params.get("version").unwrap(), "v1");
+                    assert_eq!(params.get("id").unwrap(), "1");
+                    let prefix = req.body().nested_prefix();
+                    assert_eq!(prefix.strip(req.uri().path()), "/users/1");
+                    Ok::<_, Infallible>(Response::new(()))
+                }),
+            )
+            .enclosed(UncheckedReady);
+
+        let service = Router::new()
+            .insert(
+                "/",
+                fn_service(|_: Request<RequestExt<()>>| async { Ok::<_, Infallible>(Response::new(())) }),
+            )
+            .nest("/api/:version/", api)
+            .call(())
+            .now_or_panic()
+            .unwrap();
+
+        let req = Request::builder()
+            .uri("/api/v1/users/1")
+            .body(Default::default())
+            .unwrap();
+        service.call(req).now_or_panic().unwrap();
+
+        let req = Request::builder().uri("/api/v1/users").body(Default::default()).unwrap();
+        assert!(service.call(req).now_or_panic().is_err());
+    }
+
+    #[test]
+    fn router_reload() {
+        let reload = RouterReload::new();
+
+        let service = Router::new()
+            .insert_named(
+                "user",
+                "/users/:id",
+                fn_service(|req: Request<RequestExt<()>>| async move {
+                    assert_eq!(req.body().params().get("id").unwrap(), "1");
+                    Ok::<_, Infallible>(Response::new(()))
+                }),
+            )
+            .with_reload(reload.clone())
+            .call(())
+            .now_or_panic()
+            .unwrap();
+
+        let req = |uri| Request::builder().uri(uri).body(Default::default()).unwrap();
+
+        service.call(req("/users/1")).now_or_panic().unwrap();
+
+        reload.reload([("/v2/users/:id", "user"), ("/v2/posts/:id", "post")]).unwrap();
+
+        assert!(service.call(req("/users/1")).now_or_panic().is_err());
+        assert!(service.call(req("/v2/posts/1")).now_or_panic().is_err());
+        service.call(req("/v2/users/1")).now_or_panic().unwrap();
+
+        reload.reset();
+
+        service.call(req("/users/1")).now_or_panic().unwrap();
+    }
+
+    #[test]
+    fn router_reload_nest() {
+        let reload = RouterReload::new();
+
+        let api = Router::new().insert_named(
+            "user",
+            "/users/:id",
+            fn_service(|req: Request<RequestExt<()>>| async move {
+                let prefix = req.body().nested_prefix();
+                assert_eq!(prefix.strip(req.uri().path()), "/users/1");
+                Ok::<_, Infallible>(Response::new(()))
+            }),
+        );
+
+        let service = Router::new()
+            .nest("/api/:version", api)
+            .with_reload(reload.clone())
+            .call(())
+            .now_or_panic()
+            .unwrap();
+
+        let req = |uri| Request::builder().uri(uri).body(Default::default()).unwrap();
+
+        service.call(req("/api/v1/users/1")).now_or_panic().unwrap();
+
+        reload.reload([("/v2/:version/users/:id", "user")]).unwrap();
+
+        service.call(req("/v2/v1/users/1")).now_or_panic().unwrap();
+    }
+
+    #[test]
+    fn router_host() {
+        let tenant = Router::new().insert(
+            "/users/:id",
+            fn_service(|req: Request<RequestExt<()>>| async move {
+                let params = req.body().params();
+                assert_eq!(params.get("tenant").unwrap(), "foo");
+                assert_eq!(params.get("id").unwrap(), "1");
+                Ok::<_, Infallible>(Response::new("tenant"))
+            }),
+        );
+
+        let admin = Router::new().insert(
+            "/users/:id",
+            fn_service(|_: Request<RequestExt<()>>| async { Ok::<_, Infallible>(Response::new("admin")) }),
+        );
+
+        let service = Router::new()
+            .insert(
+                "/",
+                fn_service(|_: Request<RequestExt<()>>| async { Ok::<_, Infallible>(Response::new("default")) }),
+            )
+            .host("{tenant}.example.com", tenant)
+            .host("admin.example.com", admin)
+            .call(())
+            .now_or_panic()
+            .unwrap();
+
+        let call = |host, uri| {
+            let req = Request::builder()
+                .uri(uri)
+                .header(HOST, host)
+                .body(Default::default())
+                .unwrap();
+            service.call(req).now_or_panic().map(Response::into_body)
+        };
+
+        assert_eq!(call("foo.example.com", "/users/1").unwrap(), "tenant");
+        assert_eq!(call("ADMIN.example.com:8080", "/users/1").unwrap(), "admin");
+        assert_eq!(call("foo.example.com", "/").unwrap(), "default");
+        assert!(call("example.com", "/users/1").is_err());
+
+        let req = Request::builder()
+            .uri("http://admin.example.com/users/1")
+            .body(Default::default())
+            .unwrap();
+        assert_eq!(service.call(req).now_or_panic().unwrap().into_body(), "admin");
+    }
+
+    #[test]
+    #[should_panic]
+    fn router_host_reload() {
+        let tenant = Router::new().insert(
+            "/users/:id",
+            fn_service(|_: Request<RequestExt<()>>| async { Ok::<_, Infallible>(Response::new(())) }),
+        );
+
+        let _ = Router::new()
+            .with_reload(RouterReload::new())
+            .host("{tenant}.example.com", tenant);
+    }
+
+    #[test]
+    fn router_named() {
+        let user = Router::new().insert_named(
+            "user",
+            "/users/:id",
+            fn_service(|req: Request<RequestExt<()>>| async move {
+                let urls = req.body().urls().unwrap();
+                assert_eq!(urls.url_for("user", [("version", "v2"), ("id", "2")]).unwrap(), "/api/v2/users/2");
+                Ok::<_, Infallible>(Response::new(()))
+            }),
+        );
+
+        let req = Request::builder().uri("/api/v1/users/1").body(Default::default()).unwrap();
+
+        Router::new()
+            .nest("/api/:version", user)
+            .with_urls()
+            .call(())
+            .now_or_panic()
+            .unwrap()
+            .call(req)
+            .now_or_panic()
+            .unwrap();
+
+        let req = Request::builder().uri("/users/1").body(Default::default()).unwrap();
+
+        Router::new()
+            .insert_named(
+                "user",
+                "/users/:id",
+                fn_service(|req: Request<RequestExt<()>>| async move {
+                    assert!(req.body().urls().is_none());
+                    Ok::<_, Infallible>(Response::new(()))
+                }),
+            )
+            .call(())
+            .now_or_panic()
+            .unwrap()
+            .call(req)
+            .now_or_panic()
+            .unwrap();
+    }
 }
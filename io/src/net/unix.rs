@@ -2,6 +2,7 @@ use std::{
     io,
     net::{Ipv4Addr, SocketAddr, SocketAddrV4},
     os::unix::io::{AsRawFd, RawFd},
+    path::Path,
 };
 
 use super::Stream;
@@ -11,6 +12,10 @@ pub use tokio::net::UnixListener;
 pub struct UnixStream(pub(crate) tokio::net::UnixStream);
 
 impl UnixStream {
+    pub async fn connect<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        tokio::net::UnixStream::connect(path).await.map(Self)
+    }
+
     pub fn from_std(stream: std::os::unix::net::UnixStream) -> io::Result<Self> {
         let stream = tokio::net::UnixStream::from_std(stream)?;
         Ok(Self(stream))
@@ -0,0 +1,108 @@
+use core::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use super::{client::Client, config::Config, connect, error::Error};
+
+/// The capability to request cancellation of in-progress queries on a connection.
+///
+/// Cancel request is sent through a new connection to database and it's not guaranteed to take
+/// effect. When the cancellation is effective the in-progress query would result in an error with
+/// [SqlState::QUERY_CANCELED](crate::error::SqlState::QUERY_CANCELED).
+#[derive(Clone)]
+pub struct CancelToken {
+    cfg: Config,
+    process_id: i32,
+    secret_key: i32,
+}
+
+impl CancelToken {
+    pub(crate) fn new(cfg: Config) -> Self {
+        Self {
+            cfg,
+            process_id: 0,
+            secret_key: 0,
+        }
+    }
+
+    pub(crate) fn set_key(&mut self, process_id: i32, secret_key: i32) {
+        self.process_id = process_id;
+        self.secret_key = secret_key;
+    }
+
+    /// Attempts to cancel the in-progress query on the connection associated with this token.
+    pub async fn cancel_query(&self) -> Result<(), Error> {
+        connect::cancel(&self.cfg, self.process_id, self.secret_key).await
+    }
+}
+
+impl Client {
+    /// Wrap given future and cancel in-progress query of the connection when the future is dropped
+    /// before it resolves. Cancellation happens in a detached task spawned on tokio runtime and it's
+    /// skipped when the future is dropped outside of tokio runtime.
+    ///
+    /// Database does not know which query is cancelled. A dropped future with it's query already
+    /// finished can result in cancellation of following query on the same connection.
+    ///
+    /// # Examples:
+    /// ```rust
+    /// # use std::time::Duration;
+    /// # use xitca_postgres::Client;
+    /// # async fn cancel(cli: Client) {
+    /// let stmt = cli.prepare("SELECT pg_sleep(100)", &[]).await.unwrap();
+    ///
+    /// let fut = cli.cancel_on_drop(cli.execute(stmt.as_ref(), &[]));
+    ///
+    /// // the future is dropped when timeout is reached and the in-progress query is cancelled.
+    /// let res = tokio::time::timeout(Duration::from_secs(1), fut).await;
+    /// assert!(res.is_err());
+    /// # }
+    /// ```
+    pub fn cancel_on_drop<F>(&self, fut: F) -> CancelOnDrop<F>
+    where
+        F: Future,
+    {
+        CancelOnDrop {
+            fut: Box::pin(fut),
+            token: self.cancel_token(),
+            in_flight: false,
+        }
+    }
+}
+
+/// Future for [Client::cancel_on_drop].
+pub struct CancelOnDrop<F> {
+    fut: Pin<Box<F>>,
+    token: CancelToken,
+    in_flight: bool,
+}
+
+impl<F> Future for CancelOnDrop<F>
+where
+    F: Future,
+{
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let res = this.fut.as_mut().poll(cx);
+        this.in_flight = res.is_pending();
+        res
+    }
+}
+
+impl<F> Drop for CancelOnDrop<F> {
+    fn drop(&mut self) {
+        // future never polled has not sent query to database.
+        if self.in_flight {
+            if let Ok(handle) = tokio::runtime::Handle::try_current() {
+                let token = self.token.clone();
+                handle.spawn(async move {
+                    let _ = token.cancel_query().await;
+                });
+            }
+        }
+    }
+}
@@ -6,11 +6,19 @@ use xitca_unsafe_collection::no_hash::NoHashBuilder;
 
 use tokio::sync::mpsc::UnboundedSender;
 
-use super::{error::Error, request::Request, response::Response, statement::Statement, util::lock::Lock};
+use super::{
+    cancel::CancelToken,
+    error::Error,
+    request::{ExclusiveSender, Request},
+    response::Response,
+    statement::Statement,
+    util::lock::Lock,
+};
 
 pub struct Client {
     pub(crate) tx: UnboundedSender<Request>,
     pub(crate) buf: Lock<BytesMut>,
+    pub(crate) cancel_token: CancelToken,
     cached_typeinfo: Lock<CachedTypeInfo>,
 }
 
@@ -33,10 +41,11 @@ struct CachedTypeInfo {
 }
 
 impl Client {
-    pub(crate) fn new(tx: UnboundedSender<Request>) -> Self {
+    pub(crate) fn new(tx: UnboundedSender<Request>, cancel_token: CancelToken) -> Self {
         Self {
             tx,
             buf: Lock::new(BytesMut::new()),
+            cancel_token,
             cached_typeinfo: Lock::new(CachedTypeInfo {
                 typeinfo: None,
                 typeinfo_composite: None,
@@ -50,12 +59,26 @@ impl Client {
         self.tx.is_closed()
     }
 
+    /// Constructs a cancellation token that can later be used to request cancellation of a query running
+    /// on the connection associated with this client.
+    pub fn cancel_token(&self) -> CancelToken {
+        self.cancel_token.clone()
+    }
+
     pub(crate) fn send(&self, msg: BytesMut) -> Result<Response, Error> {
         let (req, res) = Request::new_pair(msg);
         self.tx.send(req)?;
         Ok(res)
     }
 
+    // send a message to database and take exclusive access of connection until returned sender
+    // is dropped.
+    pub(crate) fn send_exclusive(&self, msg: BytesMut) -> Result<(Response, ExclusiveSender), Error> {
+        let (req, res, tx) = Request::new_exclusive(msg);
+        self.tx.send(req)?;
+        Ok((res, tx))
+    }
+
     // send a message to database without concerning a response.
     pub(crate) fn send2(&self, msg: BytesMut) -> Result<(), Error> {
         let req = Request::new(msg);
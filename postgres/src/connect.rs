@@ -1,64 +1,317 @@
+use core::{
+    future::{poll_fn, Future},
+    pin::Pin,
+};
+
 use fallible_iterator::FallibleIterator;
+use futures_core::stream::Stream;
 use postgres_protocol::{
     authentication::{self, sasl},
     message::{backend, frontend},
 };
-use xitca_io::net::TcpStream;
+use tokio::sync::mpsc::unbounded_channel;
+use xitca_io::{
+    bytes::{Buf, BytesMut},
+    io::{AsyncIo, Interest},
+    net::TcpStream,
+};
+
+#[cfg(unix)]
+use {std::path::Path, xitca_io::net::UnixStream};
 
 use super::{
+    cancel::CancelToken,
     client::Client,
-    config::{Config, Host},
-    error::AuthenticationError,
-    error::Error,
+    config::{Config, Host, LoadBalanceHosts, SslMode, TargetSessionAttrs},
+    error::{unexpected_eof_err, write_zero_err, AuthenticationError, Error, SqlState, TlsError},
+    io::buffered::BufferedIo,
 };
 
+/// boxed async task that drives the client communication to db.
+pub(crate) type Driver = Pin<Box<dyn Future<Output = Result<(), Error>> + Send>>;
+
 #[cold]
 #[inline(never)]
-pub(crate) async fn connect(cfg: &Config) -> Result<TcpStream, Error> {
+pub(crate) async fn connect(cfg: Config) -> Result<(Client, Driver), Error> {
     let hosts = cfg.get_hosts();
     let ports = cfg.get_ports();
 
+    if ports.len() > 1 && ports.len() != hosts.len() {
+        return Err(Error::config("invalid number of ports"));
+    }
+
+    // pair hosts with their ports.
+    let mut addrs = hosts
+        .iter()
+        .enumerate()
+        .map(|(i, host)| {
+            let port = match ports {
+                [] => 5432,
+                [port] => *port,
+                ports => ports[i],
+            };
+            (host, port)
+        })
+        .collect::<Vec<_>>();
+
+    if cfg.get_load_balance_hosts() == LoadBalanceHosts::Random {
+        shuffle(&mut addrs);
+    }
+
+    match cfg.get_target_session_attrs() {
+        // try standby servers first and fallback to any server.
+        TargetSessionAttrs::PreferStandby => match connect_addrs(&addrs, &cfg, TargetSessionAttrs::Standby).await {
+            Ok(res) => Ok(res),
+            Err(_) => connect_addrs(&addrs, &cfg, TargetSessionAttrs::Any).await,
+        },
+        attrs => connect_addrs(&addrs, &cfg, attrs).await,
+    }
+}
+
+async fn connect_addrs(
+    addrs: &[(&Host, u16)],
+    cfg: &Config,
+    attrs: TargetSessionAttrs,
+) -> Result<(Client, Driver), Error> {
     let mut err = None;
 
-    for host in hosts {
-        match host {
-            Host::Tcp(host) => match connect_tcp(host, ports).await {
-                Ok(stream) => return Ok(stream),
-                Err(e) => err = Some(e),
-            },
+    for (host, port) in addrs {
+        let res = match cfg.get_connect_timeout() {
+            Some(dur) => tokio::time::timeout(dur, connect_host(host, *port, cfg, attrs))
+                .await
+                .unwrap_or_else(|_| Err(timeout_err())),
+            None => connect_host(host, *port, cfg, attrs).await,
+        };
+
+        match res {
+            Ok(res) => return Ok(res),
+            Err(e) => err = Some(e),
         }
     }
 
-    Err(err.unwrap())
+    Err(err.unwrap_or_else(|| Error::config("host missing")))
+}
+
+async fn connect_host(
+    host: &Host,
+    port: u16,
+    cfg: &Config,
+    attrs: TargetSessionAttrs,
+) -> Result<(Client, Driver), Error> {
+    let token = CancelToken::new(cancel_config(cfg, host, port));
+    match host {
+        Host::Tcp(name) => {
+            let io = connect_tcp(name, port).await?;
+            prepare_tcp(io, name, cfg, token, attrs).await
+        }
+        #[cfg(unix)]
+        Host::Unix(path) => {
+            let io = connect_unix(path, port).await?;
+            // tls is not used on unix socket.
+            prepare(io, cfg, token, attrs, None).await
+        }
+    }
 }
 
-async fn connect_tcp(host: &str, ports: &[u16]) -> Result<TcpStream, Error> {
+// shuffle with randomness from std's hasher keys.
+fn shuffle<T>(items: &mut [T]) {
+    use std::{
+        collections::hash_map::RandomState,
+        hash::{BuildHasher, Hasher},
+    };
+
+    let state = RandomState::new();
+    for i in (1..items.len()).rev() {
+        let mut hasher = state.build_hasher();
+        hasher.write_usize(i);
+        let j = (hasher.finish() % (i as u64 + 1)) as usize;
+        items.swap(i, j);
+    }
+}
+
+#[cold]
+#[inline(never)]
+fn timeout_err() -> Error {
+    Error::from(std::io::Error::new(std::io::ErrorKind::TimedOut, "connect to host timed out"))
+}
+
+// config for connecting to the same host and port of an established connection.
+// credentials are not needed for cancel request.
+fn cancel_config(cfg: &Config, host: &Host, port: u16) -> Config {
+    let mut cancel_cfg = Config::new();
+    cancel_cfg.host = vec![host.clone()];
+    cancel_cfg.port = vec![port];
+    cancel_cfg.ssl_mode = cfg.ssl_mode;
+    cancel_cfg.ssl_root_cert = cfg.ssl_root_cert.clone();
+    cancel_cfg
+}
+
+#[cold]
+#[inline(never)]
+pub(crate) async fn cancel(cfg: &Config, process_id: i32, secret_key: i32) -> Result<(), Error> {
+    let mut buf = BytesMut::new();
+    frontend::cancel_request(process_id, secret_key, &mut buf);
+
+    let port = cfg.get_ports()[0];
+
+    match cfg.get_hosts().first().expect("cancel config must have host") {
+        Host::Tcp(host) => {
+            let io = connect_tcp(host, port).await?;
+            cancel_tcp(io, host, cfg, buf).await
+        }
+        #[cfg(unix)]
+        Host::Unix(path) => {
+            let io = connect_unix(path, port).await?;
+            send_cancel(io, buf).await
+        }
+    }
+}
+
+#[allow(unused_variables)]
+async fn cancel_tcp(io: TcpStream, host: &str, cfg: &Config, buf: BytesMut) -> Result<(), Error> {
+    match cfg.get_ssl_mode() {
+        SslMode::Disable => send_cancel(io, buf).await,
+        #[cfg(not(any(feature = "openssl", feature = "rustls")))]
+        SslMode::Prefer => send_cancel(io, buf).await,
+        #[cfg(not(any(feature = "openssl", feature = "rustls")))]
+        _ => Err(TlsError::NotEnabled.into()),
+        #[cfg(any(feature = "openssl", feature = "rustls"))]
+        mode => {
+            let mut io = io;
+            if super::tls::negotiate(&mut io).await? {
+                let (io, _) = super::tls::connect(io, host, cfg).await?;
+                send_cancel(io, buf).await
+            } else if mode == SslMode::Prefer {
+                send_cancel(io, buf).await
+            } else {
+                Err(TlsError::Unsupported.into())
+            }
+        }
+    }
+}
+
+// write cancel request and wait for database server closing the connection.
+async fn send_cancel<Io>(mut io: Io, mut buf: BytesMut) -> Result<(), Error>
+where
+    Io: AsyncIo,
+{
+    while !buf.is_empty() {
+        io.ready(Interest::WRITABLE).await?;
+        match std::io::Write::write(&mut io, &buf) {
+            Ok(0) => return Err(write_zero_err()),
+            Ok(n) => buf.advance(n),
+            Err(ref e) if e.kind() == std::io::ErrorKind::WouldBlock => {}
+            Err(e) => return Err(e.into()),
+        }
+    }
+
+    let mut res = [0; 8];
+
+    loop {
+        io.ready(Interest::READABLE).await?;
+        match std::io::Read::read(&mut io, &mut res) {
+            Ok(0) => return Ok(()),
+            // server does not respond to cancel request.
+            Ok(_) => return Err(unexpected_eof_err()),
+            Err(ref e) if e.kind() == std::io::ErrorKind::WouldBlock => {}
+            Err(e) => return Err(e.into()),
+        }
+    }
+}
+
+async fn connect_tcp(host: &str, port: u16) -> Result<TcpStream, Error> {
     let mut err = None;
 
-    for port in ports {
-        match tokio::net::lookup_host((host, *port)).await {
-            Ok(addrs) => {
-                for addr in addrs {
-                    match TcpStream::connect(addr).await {
-                        Ok(stream) => {
-                            let _ = stream.set_nodelay(true);
-                            return Ok(stream);
-                        }
-                        Err(e) => err = Some(e),
-                    }
-                }
+    for addr in tokio::net::lookup_host((host, port)).await? {
+        match TcpStream::connect(addr).await {
+            Ok(stream) => {
+                let _ = stream.set_nodelay(true);
+                return Ok(stream);
             }
             Err(e) => err = Some(e),
         }
     }
 
-    Err(err.unwrap().into())
+    Err(err
+        .unwrap_or_else(|| std::io::Error::new(std::io::ErrorKind::InvalidInput, "could not resolve any addresses"))
+        .into())
+}
+
+#[cfg(unix)]
+async fn connect_unix(path: &Path, port: u16) -> Result<UnixStream, Error> {
+    let stream = if matches!(path.file_name().and_then(|name| name.to_str()), Some(name) if name.starts_with(".s.PGSQL.")) {
+        UnixStream::connect(path).await?
+    } else {
+        UnixStream::connect(path.join(format!(".s.PGSQL.{port}"))).await?
+    };
+    Ok(stream)
+}
+
+#[allow(unused_variables)]
+async fn prepare_tcp(
+    io: TcpStream,
+    host: &str,
+    cfg: &Config,
+    token: CancelToken,
+    attrs: TargetSessionAttrs,
+) -> Result<(Client, Driver), Error> {
+    match cfg.get_ssl_mode() {
+        SslMode::Disable => prepare(io, cfg, token, attrs, None).await,
+        #[cfg(not(any(feature = "openssl", feature = "rustls")))]
+        SslMode::Prefer => prepare(io, cfg, token, attrs, None).await,
+        #[cfg(not(any(feature = "openssl", feature = "rustls")))]
+        _ => Err(TlsError::NotEnabled.into()),
+        #[cfg(any(feature = "openssl", feature = "rustls"))]
+        mode => {
+            let mut io = io;
+            if super::tls::negotiate(&mut io).await? {
+                let (io, channel_binding) = super::tls::connect(io, host, cfg).await?;
+                prepare(io, cfg, token, attrs, channel_binding).await
+            } else if mode == SslMode::Prefer {
+                prepare(io, cfg, token, attrs, None).await
+            } else {
+                Err(TlsError::Unsupported.into())
+            }
+        }
+    }
+}
+
+async fn prepare<Io>(
+    io: Io,
+    cfg: &Config,
+    token: CancelToken,
+    attrs: TargetSessionAttrs,
+    channel_binding: Option<sasl::ChannelBinding>,
+) -> Result<(Client, Driver), Error>
+where
+    Io: AsyncIo + Send + 'static,
+    for<'r> <Io as AsyncIo>::ReadyFuture<'r>: Send,
+{
+    let (tx, rx) = unbounded_channel();
+    let mut cli = Client::new(tx, token);
+    let handle = BufferedIo::new(io, rx).spawn();
+
+    let ret = match cli.authenticate(cfg, channel_binding).await {
+        Ok(key) => cli.check_session(attrs).await.map(|_| key),
+        Err(e) => Err(e),
+    };
+    // retrieve io regardless of authentication outcome.
+    let io = handle.into_inner().await;
+    let (process_id, secret_key) = ret?;
+
+    cli.cancel_token.set_key(process_id, secret_key);
+
+    Ok((cli, Box::pin(io.run())))
 }
 
 impl Client {
     #[cold]
     #[inline(never)]
-    pub(crate) async fn authenticate(&self, cfg: Config) -> Result<(), Error> {
+    pub(crate) async fn authenticate(
+        &self,
+        cfg: &Config,
+        mut channel_binding: Option<sasl::ChannelBinding>,
+    ) -> Result<(i32, i32), Error> {
         let mut params = vec![("client_encoding", "UTF8")];
         if let Some(user) = &cfg.user {
             params.push(("user", &**user));
@@ -76,9 +329,13 @@ impl Client {
         let msg = self.with_buf_fallible(|buf| frontend::startup_message(params, buf).map(|_| buf.split()))?;
         let mut res = self.send(msg)?;
 
+        let mut key = (0, 0);
+
         loop {
-            match res.recv().await? {
-                backend::Message::AuthenticationOk => return Ok(()),
+            match res.recv().await.map_err(auth_err)? {
+                backend::Message::AuthenticationOk => {}
+                backend::Message::BackendKeyData(body) => key = (body.process_id(), body.secret_key()),
+                backend::Message::ReadyForQuery(_) => return Ok(key),
                 backend::Message::AuthenticationCleartextPassword => {
                     let pass = cfg.get_password().ok_or(AuthenticationError::MissingPassWord)?;
                     self.send_pass(pass)?;
@@ -95,7 +352,6 @@ impl Client {
                     let mut is_scram = false;
                     let mut is_scram_plus = false;
                     let mut mechanisms = body.mechanisms();
-                    let channel_binding = None;
 
                     while let Some(mechanism) = mechanisms.next()? {
                         match mechanism {
@@ -105,9 +361,8 @@ impl Client {
                         }
                     }
 
-                    let (channel_binding, mechanism) = match (channel_binding, is_scram_plus, is_scram) {
-                        // TODO: return "unsupported SASL mechanism" error.
-                        (_, false, false) => return Err(Error::ToDo),
+                    let (channel_binding, mechanism) = match (channel_binding.take(), is_scram_plus, is_scram) {
+                        (_, false, false) => return Err(Error::from(AuthenticationError::UnsupportedMechanism)),
                         (Some(binding), true, _) => (binding, sasl::SCRAM_SHA_256_PLUS),
                         (Some(_), false, true) => (sasl::ChannelBinding::unrequested(), sasl::SCRAM_SHA_256),
                         (None, _, _) => (sasl::ChannelBinding::unsupported(), sasl::SCRAM_SHA_256),
@@ -120,7 +375,7 @@ impl Client {
                     })?;
                     self.send2(msg)?;
 
-                    match res.recv().await? {
+                    match res.recv().await.map_err(auth_err)? {
                         backend::Message::AuthenticationSaslContinue(body) => {
                             scram.update(body.data())?;
                             let msg = self.with_buf_fallible(|buf| {
@@ -128,20 +383,47 @@ impl Client {
                             })?;
                             self.send2(msg)?;
                         }
-                        _ => return Err(Error::ToDo),
+                        _ => return Err(Error::UnexpectedMessage),
                     }
 
-                    match res.recv().await? {
+                    match res.recv().await.map_err(auth_err)? {
                         backend::Message::AuthenticationSaslFinal(body) => scram.finish(body.data())?,
-                        _ => return Err(Error::ToDo),
+                        _ => return Err(Error::UnexpectedMessage),
                     }
                 }
-                backend::Message::ErrorResponse(_) => return Err(Error::from(AuthenticationError::WrongPassWord)),
                 _ => {}
             }
         }
     }
 
+    // check the session of connected server meets the requirement of target session attributes.
+    #[cold]
+    #[inline(never)]
+    async fn check_session(&self, attrs: TargetSessionAttrs) -> Result<(), Error> {
+        let (query, expect) = match attrs {
+            TargetSessionAttrs::ReadWrite => ("SHOW transaction_read_only", "off"),
+            TargetSessionAttrs::ReadOnly => ("SHOW transaction_read_only", "on"),
+            TargetSessionAttrs::Primary => ("SELECT pg_is_in_recovery()", "f"),
+            TargetSessionAttrs::Standby => ("SELECT pg_is_in_recovery()", "t"),
+            _ => return Ok(()),
+        };
+
+        let mut stream = self.query_simple(query)?;
+        let mut matched = false;
+
+        while let Some(row) = poll_fn(|cx| Pin::new(&mut stream).poll_next(cx)).await {
+            if row?.try_get(0)? == Some(expect) {
+                matched = true;
+            }
+        }
+
+        if matched {
+            Ok(())
+        } else {
+            Err(Error::SessionAttrs(attrs))
+        }
+    }
+
     #[cold]
     #[inline(never)]
     fn send_pass(&self, pass: impl AsRef<[u8]>) -> Result<(), Error> {
@@ -149,3 +431,11 @@ impl Client {
         self.send2(msg)
     }
 }
+
+// map error response of invalid password to authentication error.
+fn auth_err(e: Error) -> Error {
+    match e.code() {
+        Some(code) if code == &SqlState::INVALID_PASSWORD => Error::from(AuthenticationError::WrongPassWord),
+        _ => e,
+    }
+}
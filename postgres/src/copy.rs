@@ -0,0 +1,318 @@
+//! `COPY FROM STDIN` and `COPY TO STDOUT` support.
+
+use core::{
+    future::poll_fn,
+    pin::{pin, Pin},
+    task::{ready, Context, Poll},
+};
+
+use futures_core::stream::Stream;
+use postgres_protocol::message::{backend, frontend};
+use postgres_types::{BorrowToSql, IsNull, ToSql, Type};
+use xitca_io::bytes::{Buf, BufMut, Bytes, BytesMut};
+
+use crate::{
+    client::Client,
+    error::Error,
+    iter::slice_iter,
+    query::{encode_bind, res_to_row_affected},
+    request::ExclusiveSender,
+    response::Response,
+    statement::Statement,
+};
+
+impl Client {
+    /// Executes a `COPY FROM STDIN` statement and stream the data chunks from given stream to database.
+    /// Returns the number of rows copied.
+    ///
+    /// The client connection is exclusively occupied by the copy until it's finished. Dropping the
+    /// returned future before it resolves would abort the copy.
+    ///
+    /// # Panics
+    ///
+    /// Panics if given params slice length does not match the length of [Statement::params].
+    #[inline]
+    pub async fn copy_in<S, B>(&self, stmt: &Statement, params: &[&(dyn ToSql + Sync)], stream: S) -> Result<u64, Error>
+    where
+        S: Stream<Item = B>,
+        B: Buf,
+    {
+        self.copy_in_raw(stmt, slice_iter(params), stream).await
+    }
+
+    /// # Panics
+    ///
+    /// Panics if given params' [ExactSizeIterator::len] does not match the length of [Statement::params].
+    pub async fn copy_in_raw<I, S, B>(&self, stmt: &Statement, params: I, stream: S) -> Result<u64, Error>
+    where
+        I: IntoIterator,
+        I::IntoIter: ExactSizeIterator,
+        I::Item: BorrowToSql,
+        S: Stream<Item = B>,
+        B: Buf,
+    {
+        let buf = encode(self, stmt, params.into_iter(), false)?;
+        let (mut res, tx) = self.send_exclusive(buf)?;
+
+        // guard would abort the copy when dropped early.
+        let mut guard = CopyInGuard { tx: Some(tx) };
+
+        match res.recv().await? {
+            backend::Message::BindComplete => {}
+            _ => return Err(Error::UnexpectedMessage),
+        }
+
+        match res.recv().await? {
+            backend::Message::CopyInResponse(_) => {}
+            _ => return Err(Error::UnexpectedMessage),
+        }
+
+        let mut stream = pin!(stream);
+
+        while let Some(chunk) = poll_fn(|cx| stream.as_mut().poll_next(cx)).await {
+            let mut buf = BytesMut::new();
+            frontend::CopyData::new(chunk)?.write(&mut buf);
+            guard.send(buf).await?;
+        }
+
+        let mut buf = BytesMut::new();
+        frontend::copy_done(&mut buf);
+        frontend::sync(&mut buf);
+        guard.finish(buf).await?;
+
+        res_to_row_affected(res).await
+    }
+
+    /// Executes a `COPY TO STDOUT` statement, returning a stream of the data chunks from database.
+    ///
+    /// # Panics
+    ///
+    /// Panics if given params slice length does not match the length of [Statement::params].
+    #[inline]
+    pub async fn copy_out(&self, stmt: &Statement, params: &[&(dyn ToSql + Sync)]) -> Result<CopyOutStream, Error> {
+        self.copy_out_raw(stmt, slice_iter(params)).await
+    }
+
+    /// # Panics
+    ///
+    /// Panics if given params' [ExactSizeIterator::len] does not match the length of [Statement::params].
+    pub async fn copy_out_raw<I>(&self, stmt: &Statement, params: I) -> Result<CopyOutStream, Error>
+    where
+        I: IntoIterator,
+        I::IntoIter: ExactSizeIterator,
+        I::Item: BorrowToSql,
+    {
+        let buf = encode(self, stmt, params.into_iter(), true)?;
+        let mut res = self.send(buf)?;
+
+        match res.recv().await? {
+            backend::Message::BindComplete => {}
+            _ => return Err(Error::UnexpectedMessage),
+        }
+
+        match res.recv().await? {
+            backend::Message::CopyOutResponse(_) => {}
+            _ => return Err(Error::UnexpectedMessage),
+        }
+
+        Ok(CopyOutStream { res })
+    }
+}
+
+fn encode<I>(client: &Client, stmt: &Statement, params: I, sync: bool) -> Result<BytesMut, Error>
+where
+    I: ExactSizeIterator,
+    I::Item: BorrowToSql,
+{
+    assert_eq!(
+        stmt.params().len(),
+        params.len(),
+        "expected {} parameters but got {}",
+        stmt.params().len(),
+        params.len()
+    );
+
+    client.with_buf_fallible(|buf| {
+        encode_bind(stmt, params, "", buf)?;
+        frontend::execute("", 0, buf)?;
+        if sync {
+            frontend::sync(buf);
+        }
+        Ok(buf.split())
+    })
+}
+
+struct CopyInGuard {
+    tx: Option<ExclusiveSender>,
+}
+
+impl CopyInGuard {
+    async fn send(&mut self, msg: BytesMut) -> Result<(), Error> {
+        self.tx.as_ref().unwrap().send(msg).await?;
+        Ok(())
+    }
+
+    async fn finish(&mut self, msg: BytesMut) -> Result<(), Error> {
+        self.send(msg).await?;
+        // release exclusive access of connection.
+        self.tx = None;
+        Ok(())
+    }
+}
+
+impl Drop for CopyInGuard {
+    fn drop(&mut self) {
+        if let Some(tx) = self.tx.take() {
+            let mut buf = BytesMut::new();
+            // copy fail message is static string that can not fail.
+            frontend::copy_fail("COPY terminated by client", &mut buf).unwrap();
+            frontend::sync(&mut buf);
+
+            // database connection is still in copy mode and it must receive the fail message before
+            // exclusive access is released. when the channel is full the message is sent from a task
+            // spawned on current runtime. it's skipped when dropped outside of tokio runtime.
+            if let Err(e) = tx.try_send(buf) {
+                use tokio::sync::mpsc::error::TrySendError;
+                if let TrySendError::Full(buf) = e {
+                    if let Ok(handle) = tokio::runtime::Handle::try_current() {
+                        handle.spawn(async move {
+                            let _ = tx.send(buf).await;
+                        });
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// A stream of data chunks from `COPY TO STDOUT` statement.
+pub struct CopyOutStream {
+    res: Response,
+}
+
+impl Stream for CopyOutStream {
+    type Item = Result<Bytes, Error>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        loop {
+            match ready!(this.res.poll_recv(cx))? {
+                backend::Message::CopyData(body) => return Poll::Ready(Some(Ok(body.into_bytes()))),
+                backend::Message::CopyDone | backend::Message::CommandComplete(_) => {}
+                backend::Message::ReadyForQuery(_) => return Poll::Ready(None),
+                _ => return Poll::Ready(Some(Err(Error::UnexpectedMessage))),
+            }
+        }
+    }
+}
+
+const BINARY_HEADER: &[u8] = b"PGCOPY\n\xff\r\n\0";
+
+/// Encoder for rows of `COPY FROM STDIN (FORMAT binary)` statement.
+///
+/// # Examples:
+/// ```rust
+/// # use xitca_postgres::{copy::BinaryCopyEncoder, Type};
+/// let mut encoder = BinaryCopyEncoder::new(&[Type::INT4, Type::TEXT]);
+/// encoder.encode(&[&1i32, &"foo"]).unwrap();
+/// encoder.encode(&[&2i32, &"bar"]).unwrap();
+///
+/// // encoded bytes can be taken multiple times and sent as chunks to Client::copy_in
+/// let chunk = encoder.take();
+/// let last_chunk = encoder.finish();
+/// ```
+pub struct BinaryCopyEncoder {
+    types: Vec<Type>,
+    buf: BytesMut,
+}
+
+impl BinaryCopyEncoder {
+    /// Construct a new encoder with column types of rows.
+    pub fn new(types: &[Type]) -> Self {
+        let mut buf = BytesMut::new();
+        buf.put_slice(BINARY_HEADER);
+        // flags field.
+        buf.put_i32(0);
+        // header extension length.
+        buf.put_i32(0);
+
+        Self {
+            types: types.to_vec(),
+            buf,
+        }
+    }
+
+    /// Encode a row of values.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of values does not match the number of column types.
+    pub fn encode(&mut self, values: &[&(dyn ToSql + Sync)]) -> Result<(), Error> {
+        assert_eq!(
+            self.types.len(),
+            values.len(),
+            "expected {} values but got {}",
+            self.types.len(),
+            values.len()
+        );
+
+        let len = self.buf.len();
+        let res = self.encode_row(values);
+        if res.is_err() {
+            // remove partial encoded row.
+            self.buf.truncate(len);
+        }
+        res
+    }
+
+    fn encode_row(&mut self, values: &[&(dyn ToSql + Sync)]) -> Result<(), Error> {
+        self.buf.put_i16(self.types.len() as i16);
+
+        for (value, ty) in values.iter().zip(self.types.iter()) {
+            let idx = self.buf.len();
+            // reserve length field of value.
+            self.buf.put_i32(0);
+            let len = match value.to_sql_checked(ty, &mut self.buf).map_err(Error::ToSql)? {
+                IsNull::Yes => -1,
+                IsNull::No => i32::try_from(self.buf.len() - idx - 4)
+                    .map_err(|_| Error::ToSql("value too large to transmit".into()))?,
+            };
+            self.buf[idx..idx + 4].copy_from_slice(&len.to_be_bytes());
+        }
+
+        Ok(())
+    }
+
+    /// Take encoded bytes from encoder. The encoder can be used to encode more rows after.
+    pub fn take(&mut self) -> Bytes {
+        self.buf.split().freeze()
+    }
+
+    /// Finish encoding and return the remaining encoded bytes with binary copy trailer.
+    pub fn finish(mut self) -> Bytes {
+        self.buf.put_i16(-1);
+        self.buf.freeze()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn binary_encode() {
+        let mut encoder = BinaryCopyEncoder::new(&[Type::INT4, Type::TEXT]);
+        encoder.encode(&[&1i32, &None::<&str>]).unwrap();
+
+        let header = encoder.take();
+        assert_eq!(&header[..11], BINARY_HEADER);
+        assert_eq!(&header[11..19], &[0; 8]);
+        assert_eq!(&header[19..], &[0, 2, 0, 0, 0, 4, 0, 0, 0, 1, 255, 255, 255, 255]);
+
+        encoder.encode(&[&2i32, &"a"]).unwrap();
+        assert_eq!(
+            encoder.finish().as_ref(),
+            &[0, 2, 0, 0, 0, 4, 0, 0, 0, 2, 0, 0, 0, 1, b'a', 255, 255]
+        );
+    }
+}
@@ -1,10 +1,29 @@
+mod sql_state;
+
+pub use self::sql_state::SqlState;
+
 use std::{convert::Infallible, error, fmt, io};
 
+use fallible_iterator::FallibleIterator;
+use postgres_protocol::message::backend::ErrorFields;
 use tokio::sync::mpsc::error::SendError;
 
+use crate::{config::TargetSessionAttrs, Type};
+
 #[derive(Debug)]
 pub enum Error {
     Authentication(AuthenticationError),
+    /// error from parsing [Config](crate::Config).
+    Config(Box<dyn error::Error + Send + Sync>),
+    /// error response from database.
+    Db(Box<DbError>),
+    /// error from converting columns of a row to rust types.
+    Row(RowError),
+    /// session of connected database does not meet the required [TargetSessionAttrs].
+    SessionAttrs(TargetSessionAttrs),
+    /// error from converting rust types to values of columns.
+    ToSql(Box<dyn error::Error + Send + Sync>),
+    Tls(TlsError),
     UnexpectedMessage,
     Io(io::Error),
     ToDo,
@@ -14,6 +33,14 @@ impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match *self {
             Self::Authentication(ref e) => fmt::Display::fmt(e, f),
+            Self::Config(ref e) => write!(f, "invalid configuration: {e}"),
+            Self::Db(ref e) => fmt::Display::fmt(e, f),
+            Self::Row(ref e) => fmt::Display::fmt(e, f),
+            Self::SessionAttrs(attrs) => {
+                write!(f, "database session does not match target session attributes {attrs:?}")
+            }
+            Self::ToSql(ref e) => write!(f, "error serializing value: {e}"),
+            Self::Tls(ref e) => fmt::Display::fmt(e, f),
             Self::UnexpectedMessage => f.write_str("unexpected message from server"),
             Self::Io(ref e) => fmt::Display::fmt(e, f),
             Self::ToDo => f.write_str("error informant is yet implemented"),
@@ -23,6 +50,25 @@ impl fmt::Display for Error {
 
 impl error::Error for Error {}
 
+impl Error {
+    /// Returns the [DbError] if the error is an error response from database.
+    pub fn as_db_error(&self) -> Option<&DbError> {
+        match *self {
+            Self::Db(ref e) => Some(e),
+            _ => None,
+        }
+    }
+
+    /// Returns the SQLSTATE code if the error is an error response from database.
+    pub fn code(&self) -> Option<&SqlState> {
+        self.as_db_error().map(DbError::code)
+    }
+
+    pub(crate) fn config(e: impl Into<Box<dyn error::Error + Send + Sync>>) -> Self {
+        Self::Config(e.into())
+    }
+}
+
 impl From<Infallible> for Error {
     fn from(e: Infallible) -> Self {
         match e {}
@@ -46,6 +92,8 @@ pub enum AuthenticationError {
     MissingUserName,
     MissingPassWord,
     WrongPassWord,
+    /// none of SASL mechanisms offered by database server is supported.
+    UnsupportedMechanism,
 }
 
 impl fmt::Display for AuthenticationError {
@@ -54,6 +102,7 @@ impl fmt::Display for AuthenticationError {
             Self::MissingUserName => f.write_str("username is missing for authentication"),
             Self::MissingPassWord => f.write_str("password is missing for authentication"),
             Self::WrongPassWord => f.write_str("password is wrong for authentication"),
+            Self::UnsupportedMechanism => f.write_str("none of SASL mechanisms is supported for authentication"),
         }
     }
 }
@@ -64,6 +113,224 @@ impl From<AuthenticationError> for Error {
     }
 }
 
+/// An error response from database.
+#[derive(Debug, Clone, Default)]
+pub struct DbError {
+    severity: String,
+    code: SqlState,
+    message: String,
+    detail: Option<String>,
+    hint: Option<String>,
+    position: Option<u32>,
+    where_: Option<String>,
+    schema: Option<String>,
+    table: Option<String>,
+    column: Option<String>,
+    datatype: Option<String>,
+    constraint: Option<String>,
+    file: Option<String>,
+    line: Option<u32>,
+    routine: Option<String>,
+}
+
+impl DbError {
+    pub(crate) fn parse(fields: &mut ErrorFields<'_>) -> io::Result<Self> {
+        let mut e = DbError::default();
+
+        while let Some(field) = fields.next()? {
+            let value = String::from_utf8_lossy(field.value_bytes()).into_owned();
+            match field.type_() {
+                b'S' if e.severity.is_empty() => e.severity = value,
+                // non localized severity takes priority.
+                b'V' => e.severity = value,
+                b'C' => e.code = SqlState::from_code(&value),
+                b'M' => e.message = value,
+                b'D' => e.detail = Some(value),
+                b'H' => e.hint = Some(value),
+                b'P' => e.position = value.parse().ok(),
+                b'W' => e.where_ = Some(value),
+                b's' => e.schema = Some(value),
+                b't' => e.table = Some(value),
+                b'c' => e.column = Some(value),
+                b'd' => e.datatype = Some(value),
+                b'n' => e.constraint = Some(value),
+                b'F' => e.file = Some(value),
+                b'L' => e.line = value.parse().ok(),
+                b'R' => e.routine = Some(value),
+                _ => {}
+            }
+        }
+
+        Ok(e)
+    }
+
+    /// The severity of the error. e.g. `ERROR`, `FATAL` and `PANIC` for errors and `WARNING`, `NOTICE`,
+    /// `DEBUG`, `INFO` and `LOG` for notices.
+    pub fn severity(&self) -> &str {
+        &self.severity
+    }
+
+    /// The SQLSTATE code of the error.
+    pub fn code(&self) -> &SqlState {
+        &self.code
+    }
+
+    /// The primary human-readable error message.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// An optional secondary error message carrying more detail about the problem.
+    pub fn detail(&self) -> Option<&str> {
+        self.detail.as_deref()
+    }
+
+    /// An optional suggestion what to do about the problem.
+    pub fn hint(&self) -> Option<&str> {
+        self.hint.as_deref()
+    }
+
+    /// An optional error cursor position as an index into the original query string, counted in
+    /// characters and start from 1.
+    pub fn position(&self) -> Option<u32> {
+        self.position
+    }
+
+    /// An indication of the context in which the error occurred.
+    pub fn where_(&self) -> Option<&str> {
+        self.where_.as_deref()
+    }
+
+    /// The name of the schema containing the object associated with the error.
+    pub fn schema(&self) -> Option<&str> {
+        self.schema.as_deref()
+    }
+
+    /// The name of the table associated with the error.
+    pub fn table(&self) -> Option<&str> {
+        self.table.as_deref()
+    }
+
+    /// The name of the table column associated with the error.
+    pub fn column(&self) -> Option<&str> {
+        self.column.as_deref()
+    }
+
+    /// The name of the data type associated with the error.
+    pub fn datatype(&self) -> Option<&str> {
+        self.datatype.as_deref()
+    }
+
+    /// The name of the constraint associated with the error.
+    pub fn constraint(&self) -> Option<&str> {
+        self.constraint.as_deref()
+    }
+
+    /// The file name of the source-code location where the error was reported.
+    pub fn file(&self) -> Option<&str> {
+        self.file.as_deref()
+    }
+
+    /// The line number of the source-code location where the error was reported.
+    pub fn line(&self) -> Option<u32> {
+        self.line
+    }
+
+    /// The name of the source-code routine reporting the error.
+    pub fn routine(&self) -> Option<&str> {
+        self.routine.as_deref()
+    }
+}
+
+impl fmt::Display for DbError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.severity, self.message)?;
+        if let Some(ref detail) = self.detail {
+            write!(f, "\nDETAIL: {detail}")?;
+        }
+        if let Some(ref hint) = self.hint {
+            write!(f, "\nHINT: {hint}")?;
+        }
+        Ok(())
+    }
+}
+
+impl error::Error for DbError {}
+
+impl From<DbError> for Error {
+    fn from(e: DbError) -> Self {
+        Self::Db(Box::new(e))
+    }
+}
+
+#[derive(Debug)]
+pub enum RowError {
+    /// column with given name or index is not found in the row.
+    ColumnNotFound(String),
+    /// type of column is not compatible with the rust type.
+    WrongType {
+        column: String,
+        ty: Type,
+        rust_type: &'static str,
+    },
+    /// error from deserializing value of column.
+    FromSql {
+        column: String,
+        source: Box<dyn error::Error + Send + Sync>,
+    },
+}
+
+impl fmt::Display for RowError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::ColumnNotFound(ref column) => write!(f, "column `{column}` is not found"),
+            Self::WrongType {
+                ref column,
+                ref ty,
+                rust_type,
+            } => write!(
+                f,
+                "cannot convert column `{column}` between the Postgres type `{ty}` and the Rust type `{rust_type}`"
+            ),
+            Self::FromSql { ref column, ref source } => write!(f, "error deserializing column `{column}`: {source}"),
+        }
+    }
+}
+
+impl error::Error for RowError {}
+
+impl From<RowError> for Error {
+    fn from(e: RowError) -> Self {
+        Self::Row(e)
+    }
+}
+
+#[derive(Debug)]
+pub enum TlsError {
+    /// tls is required by [SslMode](crate::SslMode) but none of tls features is enabled.
+    NotEnabled,
+    /// tls is required by [SslMode](crate::SslMode) but database server does not support it.
+    Unsupported,
+    /// error from tls backend.
+    Backend(Box<dyn error::Error + Send + Sync>),
+}
+
+impl fmt::Display for TlsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::NotEnabled => f.write_str("tls is required but no tls feature is enabled"),
+            Self::Unsupported => f.write_str("tls is required but server does not support it"),
+            Self::Backend(ref e) => fmt::Display::fmt(e, f),
+        }
+    }
+}
+
+impl From<TlsError> for Error {
+    fn from(e: TlsError) -> Self {
+        Self::Tls(e)
+    }
+}
+
 #[cold]
 #[inline(never)]
 pub(crate) fn unexpected_eof_err() -> Error {
@@ -81,3 +348,36 @@ pub(crate) fn write_zero_err() -> Error {
         "zero byte written. remote close connection unexpectedly",
     ))
 }
+
+#[cfg(test)]
+mod test {
+    use postgres_protocol::message::backend;
+    use xitca_io::bytes::{BufMut, BytesMut};
+
+    use super::*;
+
+    #[test]
+    fn db_error() {
+        let fields = b"SERROR\0VERROR\0C23505\0Mduplicate key value violates unique constraint \"foo_pkey\"\0Dkey (id)=(1) already exists.\0tfoo\0nfoo_pkey\0\0";
+
+        let mut buf = BytesMut::new();
+        buf.put_u8(b'E');
+        buf.put_i32(fields.len() as i32 + 4);
+        buf.put_slice(fields);
+
+        let e = match backend::Message::parse(&mut buf).unwrap().unwrap() {
+            backend::Message::ErrorResponse(body) => DbError::parse(&mut body.fields()).unwrap(),
+            _ => unreachable!(),
+        };
+
+        assert_eq!(e.severity(), "ERROR");
+        assert_eq!(e.code(), &SqlState::UNIQUE_VIOLATION);
+        assert_eq!(e.table(), Some("foo"));
+        assert_eq!(e.constraint(), Some("foo_pkey"));
+        assert_eq!(e.detail(), Some("key (id)=(1) already exists."));
+        assert!(e.hint().is_none());
+
+        let e = Error::from(e);
+        assert_eq!(e.code(), Some(&SqlState::UNIQUE_VIOLATION));
+    }
+}
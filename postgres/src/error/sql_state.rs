@@ -0,0 +1,128 @@
+use std::borrow::Cow;
+
+/// A SQLSTATE error code.
+///
+/// See [postgres error codes](https://www.postgresql.org/docs/current/errcodes-appendix.html) for the
+/// full list of codes. Codes not defined as associated constant can be compared with [SqlState::code].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct SqlState(Cow<'static, str>);
+
+impl SqlState {
+    pub(crate) fn from_code(s: &str) -> Self {
+        Self(Cow::Owned(s.to_owned()))
+    }
+
+    /// Returns the error code corresponding to the SQLSTATE.
+    pub fn code(&self) -> &str {
+        &self.0
+    }
+
+    /// 00000
+    pub const SUCCESSFUL_COMPLETION: SqlState = SqlState::const_new("00000");
+
+    /// 01000
+    pub const WARNING: SqlState = SqlState::const_new("01000");
+
+    /// 08000
+    pub const CONNECTION_EXCEPTION: SqlState = SqlState::const_new("08000");
+
+    /// 08003
+    pub const CONNECTION_DOES_NOT_EXIST: SqlState = SqlState::const_new("08003");
+
+    /// 08006
+    pub const CONNECTION_FAILURE: SqlState = SqlState::const_new("08006");
+
+    /// 0A000
+    pub const FEATURE_NOT_SUPPORTED: SqlState = SqlState::const_new("0A000");
+
+    /// 22001
+    pub const STRING_DATA_RIGHT_TRUNCATION: SqlState = SqlState::const_new("22001");
+
+    /// 22003
+    pub const NUMERIC_VALUE_OUT_OF_RANGE: SqlState = SqlState::const_new("22003");
+
+    /// 22012
+    pub const DIVISION_BY_ZERO: SqlState = SqlState::const_new("22012");
+
+    /// 22P02
+    pub const INVALID_TEXT_REPRESENTATION: SqlState = SqlState::const_new("22P02");
+
+    /// 23000
+    pub const INTEGRITY_CONSTRAINT_VIOLATION: SqlState = SqlState::const_new("23000");
+
+    /// 23001
+    pub const RESTRICT_VIOLATION: SqlState = SqlState::const_new("23001");
+
+    /// 23502
+    pub const NOT_NULL_VIOLATION: SqlState = SqlState::const_new("23502");
+
+    /// 23503
+    pub const FOREIGN_KEY_VIOLATION: SqlState = SqlState::const_new("23503");
+
+    /// 23505
+    pub const UNIQUE_VIOLATION: SqlState = SqlState::const_new("23505");
+
+    /// 23514
+    pub const CHECK_VIOLATION: SqlState = SqlState::const_new("23514");
+
+    /// 23P01
+    pub const EXCLUSION_VIOLATION: SqlState = SqlState::const_new("23P01");
+
+    /// 25001
+    pub const ACTIVE_SQL_TRANSACTION: SqlState = SqlState::const_new("25001");
+
+    /// 25P02
+    pub const IN_FAILED_SQL_TRANSACTION: SqlState = SqlState::const_new("25P02");
+
+    /// 28000
+    pub const INVALID_AUTHORIZATION_SPECIFICATION: SqlState = SqlState::const_new("28000");
+
+    /// 28P01
+    pub const INVALID_PASSWORD: SqlState = SqlState::const_new("28P01");
+
+    /// 3D000
+    pub const UNDEFINED_DATABASE: SqlState = SqlState::const_new("3D000");
+
+    /// 40001
+    pub const T_R_SERIALIZATION_FAILURE: SqlState = SqlState::const_new("40001");
+
+    /// 40P01
+    pub const T_R_DEADLOCK_DETECTED: SqlState = SqlState::const_new("40P01");
+
+    /// 42501
+    pub const INSUFFICIENT_PRIVILEGE: SqlState = SqlState::const_new("42501");
+
+    /// 42601
+    pub const SYNTAX_ERROR: SqlState = SqlState::const_new("42601");
+
+    /// 42703
+    pub const UNDEFINED_COLUMN: SqlState = SqlState::const_new("42703");
+
+    /// 42883
+    pub const UNDEFINED_FUNCTION: SqlState = SqlState::const_new("42883");
+
+    /// 42P01
+    pub const UNDEFINED_TABLE: SqlState = SqlState::const_new("42P01");
+
+    /// 42P07
+    pub const DUPLICATE_TABLE: SqlState = SqlState::const_new("42P07");
+
+    /// 53300
+    pub const TOO_MANY_CONNECTIONS: SqlState = SqlState::const_new("53300");
+
+    /// 55P03
+    pub const LOCK_NOT_AVAILABLE: SqlState = SqlState::const_new("55P03");
+
+    /// 57014
+    pub const QUERY_CANCELED: SqlState = SqlState::const_new("57014");
+
+    /// 57P01
+    pub const ADMIN_SHUTDOWN: SqlState = SqlState::const_new("57P01");
+
+    /// 57P03
+    pub const CANNOT_CONNECT_NOW: SqlState = SqlState::const_new("57P03");
+
+    const fn const_new(s: &'static str) -> Self {
+        Self(Cow::Borrowed(s))
+    }
+}
@@ -20,7 +20,7 @@ use xitca_unsafe_collection::{
 
 use crate::{
     error::{unexpected_eof_err, write_zero_err, Error},
-    request::Request,
+    request::{ExclusiveReceiver, Request},
 };
 
 use super::context::Context;
@@ -30,6 +30,7 @@ pub struct BufferedIo<Io> {
     buf_write: BufWrite,
     read_buf: BytesMut,
     rx: UnboundedReceiver<Request>,
+    exclusive_rx: Option<ExclusiveReceiver>,
     ctx: Context,
 }
 
@@ -43,6 +44,7 @@ where
             buf_write: BufWrite::default(),
             read_buf: BytesMut::new(),
             rx,
+            exclusive_rx: None,
             ctx: Context::new(),
         }
     }
@@ -88,7 +90,8 @@ where
     async fn _run(&mut self) -> Result<(), Error> {
         loop {
             let want_write = self.buf_write.want_write();
-            match try_rx(&mut self.rx, &mut self.ctx)
+            let throttled = self.ctx.throttled() || self.buf_write.exceed_limit();
+            match try_rx(&mut self.rx, &mut self.exclusive_rx, throttled)
                 .select(try_io(&mut self.io, want_write))
                 .await
             {
@@ -98,6 +101,12 @@ where
                     if let Some(tx) = req.tx {
                         self.ctx.push_concurrent_req(tx);
                     }
+                    if let Some(rx) = req.exclusive {
+                        self.exclusive_rx = Some(rx);
+                    }
+                    if let Some(tx) = req.subscribe {
+                        self.ctx.push_subscriber(tx);
+                    }
                 }
                 // client is gone.
                 SelectOutput::A(None) => break,
@@ -149,12 +158,24 @@ impl<Io> Handle<Io> {
     }
 }
 
-async fn try_rx(rx: &mut UnboundedReceiver<Request>, ctx: &mut Context) -> Option<Request> {
-    if ctx.throttled() {
-        pending().await
-    } else {
-        rx.recv().await
+async fn try_rx(
+    rx: &mut UnboundedReceiver<Request>,
+    exclusive_rx: &mut Option<ExclusiveReceiver>,
+    throttled: bool,
+) -> Option<Request> {
+    if throttled {
+        return pending().await;
+    }
+
+    // exclusive request prevent other requests from interleaving until it's released.
+    if let Some(ex) = exclusive_rx {
+        match ex.recv().await {
+            Some(msg) => return Some(Request::new(msg)),
+            None => *exclusive_rx = None,
+        }
     }
+
+    rx.recv().await
 }
 
 fn try_io<Io>(io: &mut Io, want_write: bool) -> Io::ReadyFuture<'_>
@@ -170,6 +191,8 @@ where
     io.ready(interest)
 }
 
+const WRITE_BUF_LIMIT: usize = 64 * 1024;
+
 #[derive(Default)]
 struct BufWrite {
     buf: BytesMut,
@@ -187,6 +210,11 @@ impl BufWrite {
         !self.buf.is_empty() || self.want_flush
     }
 
+    // stop taking new messages when too many bytes are waiting to be written.
+    fn exceed_limit(&self) -> bool {
+        self.buf.len() >= WRITE_BUF_LIMIT
+    }
+
     fn write<Io>(&mut self, io: &mut Io) -> Result<(), Error>
     where
         Io: io::Write,
@@ -1,15 +1,18 @@
 use std::collections::VecDeque;
 
+use postgres_protocol::message::backend;
 use xitca_io::bytes::BytesMut;
 
 use crate::{
     error::Error,
+    notification::{AsyncMessage, AsyncMessageSender},
     response::{ResponseMessage, ResponseSender},
 };
 
 pub(super) struct Context {
     concurrent_res: VecDeque<ResponseSender>,
     exclusive_res: Option<ResponseSender>,
+    subscribers: Vec<AsyncMessageSender>,
 }
 
 impl Context {
@@ -17,6 +20,7 @@ impl Context {
         Self {
             concurrent_res: VecDeque::new(),
             exclusive_res: None,
+            subscribers: Vec::new(),
         }
     }
 
@@ -36,21 +40,41 @@ impl Context {
         self.concurrent_res.push_back(tx);
     }
 
+    pub(super) fn push_subscriber(&mut self, tx: AsyncMessageSender) {
+        self.subscribers.push(tx);
+    }
+
     pub(super) fn try_decode(&mut self, buf: &mut BytesMut) -> Result<(), Error> {
         while let Some(res) = ResponseMessage::try_from_buf(buf)? {
-            if let ResponseMessage::Normal { buf, complete } = res {
-                let _ = self
-                    .concurrent_res
-                    .front_mut()
-                    .expect("Out of bound must not happen")
-                    .send(buf);
-
-                if complete {
-                    let _ = self.concurrent_res.pop_front();
+            match res {
+                ResponseMessage::Normal { buf, complete } => {
+                    let _ = self
+                        .concurrent_res
+                        .front_mut()
+                        .expect("Out of bound must not happen")
+                        .send(buf);
+
+                    if complete {
+                        let _ = self.concurrent_res.pop_front();
+                    }
                 }
+                ResponseMessage::Async(msg) => self.try_dispatch(msg)?,
             }
         }
 
         Ok(())
     }
+
+    // dispatch asynchronous message to all subscribers and remove the ones that are gone.
+    fn try_dispatch(&mut self, msg: backend::Message) -> Result<(), Error> {
+        if self.subscribers.is_empty() {
+            return Ok(());
+        }
+
+        if let Some(msg) = AsyncMessage::try_from_message(msg)? {
+            self.subscribers.retain(|tx| tx.send(msg.clone()).is_ok());
+        }
+
+        Ok(())
+    }
 }
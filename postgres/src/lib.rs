@@ -3,29 +3,88 @@
 
 //! A postgresql client on top of [rust-postgres](https://github.com/sfackler/rust-postgres/).
 
+mod cancel;
 mod client;
 mod column;
 mod config;
 mod connect;
 mod io;
 mod iter;
+mod portal;
 mod prepare;
 mod query;
 mod request;
 mod response;
 mod row;
+#[cfg(any(feature = "openssl", feature = "rustls"))]
+mod tls;
+mod transaction;
 mod util;
 
+#[cfg(feature = "codegen")]
+pub mod codegen {
+    /// Derive macro for [FromRow](crate::FromRow) trait on struct with named fields.
+    ///
+    /// Every field is deserialized from the column with the same name. Use `Option<T>` as field type
+    /// for nullable column.
+    ///
+    /// # Example:
+    /// ```rust
+    /// # use xitca_postgres::{codegen::FromRow, statement::Statement, Client};
+    /// #[derive(FromRow)]
+    /// struct User {
+    ///     id: i32,
+    ///     // deserialize from column with given name.
+    ///     #[rename = "user_name"]
+    ///     name: String,
+    ///     // nullable column.
+    ///     email: Option<String>,
+    ///     // deserialize from the same row.
+    ///     #[flatten]
+    ///     meta: Meta,
+    /// }
+    ///
+    /// #[derive(FromRow)]
+    /// struct Meta {
+    ///     created_at: i64,
+    /// }
+    ///
+    /// // struct can borrow from row with it's lifetime.
+    /// #[derive(FromRow)]
+    /// struct UserRef<'a> {
+    ///     id: i32,
+    ///     #[rename = "user_name"]
+    ///     name: &'a str,
+    /// }
+    ///
+    /// # async fn query(cli: Client, stmt: Statement) {
+    /// let users = cli.query_as::<User>(&stmt, &[]).await.unwrap();
+    /// # }
+    /// ```
+    pub use xitca_codegen::FromRow;
+}
+
+// derive macros refer to items with absolute path of crate name.
+#[cfg(all(test, feature = "codegen"))]
+extern crate self as xitca_postgres;
+pub mod copy;
 pub mod error;
+pub mod notification;
+#[cfg(feature = "pool")]
+pub mod pool;
 pub mod statement;
 
 pub use postgres_types::{ToSql, Type};
 
 pub use self::{
+    cancel::{CancelOnDrop, CancelToken},
     client::Client,
-    config::Config,
+    config::{Config, LoadBalanceHosts, SslMode, TargetSessionAttrs},
     iter::AsyncIterator,
-    row::{Row, RowSimple},
+    notification::{AsyncMessage, Notifications},
+    portal::Portal,
+    row::{FromRow, Row, RowSimple},
+    transaction::{IsolationLevel, Transaction, TransactionBuilder},
 };
 
 use self::error::Error;
@@ -83,18 +142,7 @@ where
     /// ```
     pub async fn connect(self) -> Result<(Client, impl std::future::Future<Output = Result<(), Error>> + Send), Error> {
         let cfg = Config::try_from(self.cfg)?;
-        let io = connect::connect(&cfg).await?;
-
-        let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
-        let cli = Client::new(tx);
-        let handle = io::buffered::BufferedIo::new(io, rx).spawn();
-
-        let ret = cli.authenticate(cfg).await;
-        // retrieve io regardless of authentication outcome.
-        let io = handle.into_inner().await;
-        ret?;
-
-        Ok((cli, io.run()))
+        connect::connect(cfg).await
     }
 }
 
@@ -0,0 +1,120 @@
+//! asynchronous notification and notice messages from database.
+
+use core::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use futures_core::stream::Stream;
+use postgres_protocol::message::backend;
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
+
+use super::{
+    client::Client,
+    error::{DbError, Error},
+    request::Request,
+};
+
+pub(crate) type AsyncMessageSender = UnboundedSender<AsyncMessage>;
+
+/// An asynchronous message from database that is not a response to any query.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub enum AsyncMessage {
+    /// A notice.
+    ///
+    /// Notices use the same format as errors, but aren't "errors" per-se.
+    Notice(DbError),
+    /// A notification.
+    ///
+    /// Connections can subscribe to notifications with the `LISTEN` command.
+    Notification(Notification),
+}
+
+impl AsyncMessage {
+    // try to parse backend message. return None when message is not a notice or notification.
+    pub(crate) fn try_from_message(msg: backend::Message) -> Result<Option<Self>, Error> {
+        match msg {
+            backend::Message::NoticeResponse(body) => {
+                let notice = DbError::parse(&mut body.fields())?;
+                Ok(Some(Self::Notice(notice)))
+            }
+            backend::Message::NotificationResponse(body) => Ok(Some(Self::Notification(Notification {
+                process_id: body.process_id(),
+                channel: body.channel()?.to_owned(),
+                payload: body.message()?.to_owned(),
+            }))),
+            _ => Ok(None),
+        }
+    }
+}
+
+/// An asynchronous notification from `NOTIFY` command.
+#[derive(Clone, Debug)]
+pub struct Notification {
+    process_id: i32,
+    channel: String,
+    payload: String,
+}
+
+impl Notification {
+    /// The process ID of the notifying backend process.
+    pub fn process_id(&self) -> i32 {
+        self.process_id
+    }
+
+    /// The name of the channel that the notify has been raised on.
+    pub fn channel(&self) -> &str {
+        &self.channel
+    }
+
+    /// The "payload" string passed from the notifying process.
+    pub fn payload(&self) -> &str {
+        &self.payload
+    }
+}
+
+impl Client {
+    /// Subscribe to asynchronous messages from database.
+    ///
+    /// Every subscriber receives all notifications and notices arrived after the subscription.
+    /// Use `LISTEN` command to subscribe notifications from given channel.
+    ///
+    /// # Examples:
+    /// ```rust
+    /// # use xitca_postgres::{AsyncMessage, Client};
+    /// # async fn listen(cli: Client) {
+    /// use std::future::poll_fn;
+    /// use std::pin::Pin;
+    /// use futures_core::stream::Stream;
+    ///
+    /// let mut notifications = cli.notifications().unwrap();
+    /// cli.execute_simple("LISTEN cache_invalidation").await.unwrap();
+    ///
+    /// while let Some(msg) = poll_fn(|cx| Pin::new(&mut notifications).poll_next(cx)).await {
+    ///     if let AsyncMessage::Notification(n) = msg {
+    ///         println!("{}: {}", n.channel(), n.payload());
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    pub fn notifications(&self) -> Result<Notifications, Error> {
+        let (tx, rx) = unbounded_channel();
+        self.tx.send(Request::new_subscribe(tx))?;
+        Ok(Notifications { rx })
+    }
+}
+
+/// A stream of [AsyncMessage]. The stream ends when database connection is closed.
+pub struct Notifications {
+    rx: UnboundedReceiver<AsyncMessage>,
+}
+
+impl Stream for Notifications {
+    type Item = AsyncMessage;
+
+    #[inline]
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.get_mut().rx.poll_recv(cx)
+    }
+}
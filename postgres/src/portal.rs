@@ -0,0 +1,123 @@
+//! named portal for incremental fetching rows of a query.
+
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+use postgres_protocol::message::{backend, frontend};
+use postgres_types::BorrowToSql;
+
+use super::{
+    client::Client,
+    error::Error,
+    iter::slice_iter,
+    query::{encode_bind, RowStreamGat},
+    statement::Statement,
+    transaction::Transaction,
+    ToSql,
+};
+
+impl Transaction<'_> {
+    /// Binds a statement to a set of parameters, creating a [Portal] which can be incrementally queried.
+    ///
+    /// Portals only last for the duration of the transaction in which they are created, and can only be
+    /// used on the connection that created them.
+    ///
+    /// # Panics
+    ///
+    /// Panics if given params slice length does not match the length of [Statement::params].
+    #[inline]
+    pub async fn bind<'s>(&self, stmt: &'s Statement, params: &[&(dyn ToSql + Sync)]) -> Result<Portal<'_, 's>, Error> {
+        self.bind_raw(stmt, slice_iter(params)).await
+    }
+
+    /// # Panics
+    ///
+    /// Panics if given params' [ExactSizeIterator::len] does not match the length of [Statement::params].
+    pub async fn bind_raw<'s, I>(&self, stmt: &'s Statement, params: I) -> Result<Portal<'_, 's>, Error>
+    where
+        I: IntoIterator,
+        I::IntoIter: ExactSizeIterator,
+        I::Item: BorrowToSql,
+    {
+        let params = params.into_iter();
+
+        assert_eq!(
+            stmt.params().len(),
+            params.len(),
+            "expected {} parameters but got {}",
+            stmt.params().len(),
+            params.len()
+        );
+
+        let name = format!("p{}", NEXT_ID.fetch_add(1, Ordering::Relaxed));
+
+        let buf = self.with_buf_fallible(|buf| {
+            encode_bind(stmt, params, &name, buf)?;
+            frontend::sync(buf);
+            Ok::<_, Error>(buf.split())
+        })?;
+
+        let mut res = self.send(buf)?;
+
+        match res.recv().await? {
+            backend::Message::BindComplete => {}
+            _ => return Err(Error::UnexpectedMessage),
+        }
+
+        Ok(Portal {
+            client: self,
+            name,
+            stmt,
+        })
+    }
+
+    /// Continues execution of a portal, returning a stream of at most `max_rows` resulting rows.
+    ///
+    /// Unlike [Client::query_raw_gat], portals can be incrementally evaluated by limiting the number of rows
+    /// returned in each call. A `max_rows` of 0 would fetch all the remaining rows.
+    pub async fn query_portal<'p>(&self, portal: &'p Portal<'_, '_>, max_rows: i32) -> Result<RowStreamGat<'p>, Error> {
+        let buf = self.with_buf_fallible(|buf| {
+            frontend::execute(&portal.name, max_rows, buf)?;
+            frontend::sync(buf);
+            Ok::<_, Error>(buf.split())
+        })?;
+
+        let res = self.send(buf)?;
+
+        Ok(RowStreamGat::new(portal.stmt.columns(), res))
+    }
+}
+
+/// A portal of a bound statement that can be incrementally queried with [Transaction::query_portal].
+///
+/// The portal is closed when dropped.
+pub struct Portal<'c, 's> {
+    client: &'c Client,
+    name: String,
+    stmt: &'s Statement,
+}
+
+impl Portal<'_, '_> {
+    /// The statement portal is bound to.
+    pub fn statement(&self) -> &Statement {
+        self.stmt
+    }
+}
+
+impl Drop for Portal<'_, '_> {
+    fn drop(&mut self) {
+        if !self.client.closed() {
+            let res = self.client.with_buf_fallible(|b| {
+                frontend::close(b'P', &self.name, b).map(|_| {
+                    frontend::sync(b);
+                    b.split()
+                })
+            });
+
+            if let Ok(msg) = res {
+                let _f = self.client.send(msg);
+            }
+        }
+    }
+}
+
+static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
@@ -1,2 +1,4 @@
 mod base;
 mod simple;
+
+pub(crate) use self::base::{encode_bind, res_to_row_affected, RowStreamGat};
@@ -71,11 +71,9 @@ impl Client {
         I::IntoIter: ExactSizeIterator,
         I::Item: BorrowToSql,
     {
-        self.bind(stmt, params).await.map(|res| RowStreamGat {
-            col: stmt.columns(),
-            res,
-            ranges: Vec::new(),
-        })
+        self.bind(stmt, params)
+            .await
+            .map(|res| RowStreamGat::new(stmt.columns(), res))
     }
 
     /// Executes a statement, returning the number of rows modified.
@@ -145,7 +143,7 @@ where
     })
 }
 
-fn encode_bind<I>(stmt: &Statement, params: I, portal: &str, buf: &mut BytesMut) -> Result<(), Error>
+pub(crate) fn encode_bind<I>(stmt: &Statement, params: I, portal: &str, buf: &mut BytesMut) -> Result<(), Error>
 where
     I: ExactSizeIterator,
     I::Item: BorrowToSql,
@@ -170,12 +168,12 @@ where
 
     match r {
         Ok(()) => Ok(()),
-        Err(frontend::BindError::Conversion(_)) => Err(Error::ToDo),
-        Err(frontend::BindError::Serialization(_)) => Err(Error::ToDo),
+        Err(frontend::BindError::Conversion(e)) => Err(Error::ToSql(e)),
+        Err(frontend::BindError::Serialization(e)) => Err(Error::ToSql(Box::new(e))),
     }
 }
 
-pub(super) async fn res_to_row_affected(mut res: Response) -> Result<u64, Error> {
+pub(crate) async fn res_to_row_affected(mut res: Response) -> Result<u64, Error> {
     let mut rows = 0;
     loop {
         match res.recv().await? {
@@ -228,6 +226,16 @@ pub struct RowStreamGat<'a> {
     ranges: Vec<Option<Range<usize>>>,
 }
 
+impl<'a> RowStreamGat<'a> {
+    pub(crate) fn new(col: &'a [Column], res: Response) -> Self {
+        Self {
+            col,
+            res,
+            ranges: Vec::new(),
+        }
+    }
+}
+
 impl<'a> AsyncIterator for RowStreamGat<'a> {
     type Future<'f> = impl Future<Output = Option<Self::Item<'f>>> + Send where 'a: 'f;
     type Item<'i> = Result<RowGat<'i>, Error> where 'a: 'i;
@@ -252,3 +260,18 @@ impl<'a> AsyncIterator for RowStreamGat<'a> {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use postgres_types::{ToSql, Type};
+
+    use super::*;
+
+    #[test]
+    fn bind_to_sql_error() {
+        let stmt = Statement::new(String::new(), vec![Type::TEXT], Vec::new());
+        let mut buf = BytesMut::new();
+        let e = encode_bind(&stmt, slice_iter(&[&1i32 as &(dyn ToSql + Sync)]), "", &mut buf).unwrap_err();
+        assert!(matches!(e, Error::ToSql(_)));
+    }
+}
@@ -39,7 +39,7 @@ impl Client {
         super::base::res_to_row_affected(res).await
     }
 
-    fn simple(&self, stmt: &str) -> Result<Response, Error> {
+    pub(crate) fn simple(&self, stmt: &str) -> Result<Response, Error> {
         let buf = self.with_buf_fallible(|buf| frontend::query(stmt, buf).map(|_| buf.split()))?;
         self.send(buf)
     }
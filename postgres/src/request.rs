@@ -1,21 +1,64 @@
-use tokio::sync::mpsc::unbounded_channel;
+use tokio::sync::mpsc::{channel, unbounded_channel, Receiver, Sender};
 use xitca_io::bytes::BytesMut;
 
-use super::{response::Response, response::ResponseSender};
+use super::{notification::AsyncMessageSender, response::Response, response::ResponseSender};
+
+pub type ExclusiveSender = Sender<BytesMut>;
+
+pub type ExclusiveReceiver = Receiver<BytesMut>;
 
 pub struct Request {
     pub(crate) tx: Option<ResponseSender>,
     pub(crate) msg: BytesMut,
+    pub(crate) exclusive: Option<ExclusiveReceiver>,
+    pub(crate) subscribe: Option<AsyncMessageSender>,
 }
 
 impl Request {
     // a Request that does not care for a response from database.
     pub(crate) fn new(msg: BytesMut) -> Self {
-        Self { tx: None, msg }
+        Self {
+            tx: None,
+            msg,
+            exclusive: None,
+            subscribe: None,
+        }
     }
 
     pub(crate) fn new_pair(msg: BytesMut) -> (Self, Response) {
         let (tx, rx) = unbounded_channel();
-        (Self { tx: Some(tx), msg }, Response::new(rx))
+        let req = Self {
+            tx: Some(tx),
+            msg,
+            exclusive: None,
+            subscribe: None,
+        };
+        (req, Response::new(rx))
+    }
+
+    // a Request that take exclusive access of database connection. following messages of it are
+    // sent through the returned sender and the access is released when sender is dropped.
+    pub(crate) fn new_exclusive(msg: BytesMut) -> (Self, Response, ExclusiveSender) {
+        let (tx, rx) = unbounded_channel();
+        let (tx_ex, rx_ex) = channel(EXCLUSIVE_BACKLOG);
+        let req = Self {
+            tx: Some(tx),
+            msg,
+            exclusive: Some(rx_ex),
+            subscribe: None,
+        };
+        (req, Response::new(rx), tx_ex)
+    }
+
+    // a Request that subscribe to asynchronous messages from database.
+    pub(crate) fn new_subscribe(tx: AsyncMessageSender) -> Self {
+        Self {
+            tx: None,
+            msg: BytesMut::new(),
+            exclusive: None,
+            subscribe: Some(tx),
+        }
     }
 }
+
+const EXCLUSIVE_BACKLOG: usize = 8;
@@ -7,7 +7,7 @@ use postgres_protocol::message::backend;
 use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
 use xitca_io::bytes::BytesMut;
 
-use super::error::{unexpected_eof_err, Error};
+use super::error::{unexpected_eof_err, DbError, Error};
 
 pub struct Response {
     rx: ResponseReceiver,
@@ -39,8 +39,7 @@ impl Response {
         }
 
         match backend::Message::parse(&mut self.buf)? {
-            // TODO: error response.
-            Some(backend::Message::ErrorResponse(_body)) => Poll::Ready(Err(Error::ToDo)),
+            Some(backend::Message::ErrorResponse(body)) => Poll::Ready(Err(DbError::parse(&mut body.fields())?.into())),
             Some(msg) => Poll::Ready(Ok(msg)),
             // TODO: partial response.
             None => Poll::Ready(Err(Error::ToDo)),
@@ -1,6 +1,8 @@
 //! Rows. mostly copy/paste from `tokio-postgres`
 
+mod from_row;
 mod traits;
 mod types;
 
+pub use from_row::FromRow;
 pub use types::{Row, RowGat, RowSimple, RowSimpleGat};
@@ -0,0 +1,165 @@
+use core::{future::poll_fn, pin::Pin};
+
+use futures_core::stream::Stream;
+
+use crate::{client::Client, error::Error, statement::Statement, ToSql};
+
+use super::Row;
+
+/// A trait for types that can be constructed from a [Row].
+///
+/// With `codegen` feature enabled it can be derived with `codegen::FromRow` derive macro.
+pub trait FromRow<'r>: Sized {
+    fn from_row(row: &'r Row<'r>) -> Result<Self, Error>;
+}
+
+impl Client {
+    /// Executes a statement and map the resulting rows into type implementing [FromRow].
+    ///
+    /// # Panics
+    ///
+    /// Panics if given params slice length does not match the length of [Statement::params].
+    pub async fn query_as<T>(&self, stmt: &Statement, params: &[&(dyn ToSql + Sync)]) -> Result<Vec<T>, Error>
+    where
+        T: for<'r> FromRow<'r>,
+    {
+        let mut stream = self.query(stmt, params).await?;
+        let mut res = Vec::new();
+        while let Some(row) = poll_fn(|cx| Pin::new(&mut stream).poll_next(cx)).await {
+            res.push(T::from_row(&row?)?);
+        }
+        Ok(res)
+    }
+}
+
+#[cfg(all(test, feature = "codegen"))]
+mod test {
+    use postgres_protocol::message::backend;
+    use xitca_io::bytes::{BufMut, BytesMut};
+
+    use crate::{codegen::FromRow, column::Column, error::RowError, Type};
+
+    use super::*;
+
+    #[derive(FromRow)]
+    struct User {
+        id: i32,
+        #[rename = "user_name"]
+        name: String,
+        email: Option<String>,
+        #[flatten]
+        meta: Meta,
+    }
+
+    #[derive(FromRow)]
+    struct Meta {
+        created_at: i64,
+    }
+
+    #[derive(FromRow)]
+    struct UserRef<'a> {
+        #[rename = "user_name"]
+        name: &'a str,
+    }
+
+    #[allow(dead_code)]
+    #[derive(FromRow)]
+    struct Missing {
+        missing: i32,
+    }
+
+    #[allow(dead_code)]
+    #[derive(FromRow)]
+    struct WrongType {
+        id: String,
+    }
+
+    #[allow(dead_code)]
+    #[derive(FromRow)]
+    struct NotNull {
+        email: String,
+    }
+
+    fn columns() -> Vec<Column> {
+        vec![
+            Column::new("id", Type::INT4),
+            Column::new("user_name", Type::TEXT),
+            Column::new("email", Type::TEXT),
+            Column::new("created_at", Type::INT8),
+        ]
+    }
+
+    fn row<'a>(columns: &'a [Column], email: Option<&str>) -> Row<'a> {
+        let values: [Option<&[u8]>; 4] = [
+            Some(&1i32.to_be_bytes()),
+            Some(b"foo"),
+            email.map(str::as_bytes),
+            Some(&2i64.to_be_bytes()),
+        ];
+
+        let mut body = BytesMut::new();
+        body.put_i16(values.len() as i16);
+        for value in values {
+            match value {
+                Some(value) => {
+                    body.put_i32(value.len() as i32);
+                    body.put_slice(value);
+                }
+                None => body.put_i32(-1),
+            }
+        }
+
+        let mut buf = BytesMut::new();
+        buf.put_u8(b'D');
+        buf.put_i32(body.len() as i32 + 4);
+        buf.put_slice(&body);
+
+        match backend::Message::parse(&mut buf).unwrap().unwrap() {
+            backend::Message::DataRow(body) => Row::try_new(columns, body).unwrap(),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn derive() {
+        let columns = columns();
+
+        let row1 = row(&columns, None);
+        let user = User::from_row(&row1).unwrap();
+        assert_eq!(user.id, 1);
+        assert_eq!(user.name, "foo");
+        assert_eq!(user.email, None);
+        assert_eq!(user.meta.created_at, 2);
+
+        let row2 = row(&columns, Some("foo@bar.com"));
+        let user = User::from_row(&row2).unwrap();
+        assert_eq!(user.email.as_deref(), Some("foo@bar.com"));
+
+        let user = UserRef::from_row(&row2).unwrap();
+        assert_eq!(user.name, "foo");
+    }
+
+    #[test]
+    fn derive_error() {
+        let columns = columns();
+        let row = row(&columns, None);
+
+        match Missing::from_row(&row) {
+            Err(Error::Row(RowError::ColumnNotFound(column))) => assert_eq!(column, "missing"),
+            _ => panic!("expect column not found error"),
+        }
+
+        match WrongType::from_row(&row) {
+            Err(Error::Row(RowError::WrongType { column, ty, .. })) => {
+                assert_eq!(column, "id");
+                assert_eq!(ty, Type::INT4);
+            }
+            _ => panic!("expect wrong type error"),
+        }
+
+        match NotNull::from_row(&row) {
+            Err(Error::Row(RowError::FromSql { column, .. })) => assert_eq!(column, "email"),
+            _ => panic!("expect from sql error"),
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use core::{fmt, marker::PhantomData, ops::Range};
+use core::{any::type_name, fmt, marker::PhantomData, ops::Range};
 
 use std::sync::Arc;
 
@@ -6,7 +6,11 @@ use fallible_iterator::FallibleIterator;
 use postgres_protocol::message::backend::DataRowBody;
 use postgres_types::FromSql;
 
-use crate::{column::Column, error::Error, Type};
+use crate::{
+    column::Column,
+    error::{Error, RowError},
+    Type,
+};
 
 use super::traits::RowIndexAndType;
 
@@ -86,14 +90,19 @@ impl<'a> Row<'a> {
         T: FromSql<'f>,
         'a: 'f,
     {
-        let (idx, ty) = idx.__from_columns(self.columns()).ok_or(Error::ToDo)?;
+        let (i, ty) = idx
+            .__from_columns(self.columns())
+            .ok_or_else(|| RowError::ColumnNotFound(idx.to_string()))?;
 
         if !T::accepts(ty) {
-            return Err(Error::ToDo);
-            // return Err(Error::from_sql(Box::new(WrongType::new::<T>(ty.clone())), idx));
+            return Err(Error::from(RowError::WrongType {
+                column: idx.to_string(),
+                ty: ty.clone(),
+                rust_type: type_name::<T>(),
+            }));
         }
 
-        FromSql::from_sql_nullable(ty, self.col_buffer(idx)).map_err(|_| Error::ToDo)
+        FromSql::from_sql_nullable(ty, self.col_buffer(i)).map_err(|source| from_sql_err(idx, source))
     }
 }
 
@@ -112,8 +121,10 @@ impl RowSimple {
 
     /// Like `SimpleQueryRow::get`, but returns a `Result` rather than panicking.
     pub fn try_get(&self, idx: impl RowIndexAndType + fmt::Display) -> Result<Option<&str>, Error> {
-        let (idx, _) = idx.__from_columns(self.columns()).ok_or(Error::ToDo)?;
-        FromSql::from_sql_nullable(&Type::TEXT, self.col_buffer(idx)).map_err(|_| Error::ToDo)
+        let (i, _) = idx
+            .__from_columns(self.columns())
+            .ok_or_else(|| RowError::ColumnNotFound(idx.to_string()))?;
+        FromSql::from_sql_nullable(&Type::TEXT, self.col_buffer(i)).map_err(|source| from_sql_err(idx, source))
     }
 }
 
@@ -208,14 +219,19 @@ impl<'r> RowGat<'r> {
         T: FromSql<'f>,
         'r: 'f,
     {
-        let (idx, ty) = idx.__from_columns(self.columns()).ok_or(Error::ToDo)?;
+        let (i, ty) = idx
+            .__from_columns(self.columns())
+            .ok_or_else(|| RowError::ColumnNotFound(idx.to_string()))?;
 
         if !T::accepts(ty) {
-            return Err(Error::ToDo);
-            // return Err(Error::from_sql(Box::new(WrongType::new::<T>(ty.clone())), idx));
+            return Err(Error::from(RowError::WrongType {
+                column: idx.to_string(),
+                ty: ty.clone(),
+                rust_type: type_name::<T>(),
+            }));
         }
 
-        FromSql::from_sql_nullable(ty, self.col_buffer(idx)).map_err(|_| Error::ToDo)
+        FromSql::from_sql_nullable(ty, self.col_buffer(i)).map_err(|source| from_sql_err(idx, source))
     }
 }
 
@@ -234,7 +250,18 @@ impl RowSimpleGat<'_> {
 
     /// Like `SimpleQueryRow::get`, but returns a `Result` rather than panicking.
     pub fn try_get(&self, idx: impl RowIndexAndType + fmt::Display) -> Result<Option<&str>, Error> {
-        let (idx, _) = idx.__from_columns(self.columns()).ok_or(Error::ToDo)?;
-        FromSql::from_sql_nullable(&Type::TEXT, self.col_buffer(idx)).map_err(|_| Error::ToDo)
+        let (i, _) = idx
+            .__from_columns(self.columns())
+            .ok_or_else(|| RowError::ColumnNotFound(idx.to_string()))?;
+        FromSql::from_sql_nullable(&Type::TEXT, self.col_buffer(i)).map_err(|source| from_sql_err(idx, source))
     }
 }
+
+#[cold]
+#[inline(never)]
+fn from_sql_err(idx: impl fmt::Display, source: Box<dyn std::error::Error + Send + Sync>) -> Error {
+    Error::from(RowError::FromSql {
+        column: idx.to_string(),
+        source,
+    })
+}
@@ -0,0 +1,54 @@
+//! tls transport for database connection.
+//!
+//! When both `openssl` and `rustls` features are enabled `openssl` is used as tls backend.
+
+#[cfg(feature = "openssl")]
+mod openssl;
+#[cfg(all(feature = "rustls", not(feature = "openssl")))]
+mod rustls;
+
+#[cfg(feature = "openssl")]
+pub(crate) use self::openssl::connect;
+#[cfg(all(feature = "rustls", not(feature = "openssl")))]
+pub(crate) use self::rustls::connect;
+
+use std::io;
+
+use postgres_protocol::message::frontend;
+use xitca_io::{
+    bytes::{Buf, BytesMut},
+    io::{AsyncIo, Interest},
+};
+
+use crate::error::{unexpected_eof_err, write_zero_err, Error};
+
+/// send SSLRequest to database server and return true when server is willing to proceed with tls.
+pub(crate) async fn negotiate<Io>(io: &mut Io) -> Result<bool, Error>
+where
+    Io: AsyncIo,
+{
+    let mut buf = BytesMut::new();
+    frontend::ssl_request(&mut buf);
+
+    while !buf.is_empty() {
+        io.ready(Interest::WRITABLE).await?;
+        match io::Write::write(io, &buf) {
+            Ok(0) => return Err(write_zero_err()),
+            Ok(n) => buf.advance(n),
+            Err(ref e) if e.kind() == io::ErrorKind::WouldBlock => {}
+            Err(e) => return Err(e.into()),
+        }
+    }
+
+    let mut res = [0];
+
+    loop {
+        io.ready(Interest::READABLE).await?;
+        match io::Read::read(io, &mut res) {
+            Ok(0) => return Err(unexpected_eof_err()),
+            Ok(_) => return Ok(res[0] == b'S'),
+            Err(ref e) if e.kind() == io::ErrorKind::WouldBlock => {}
+            Err(e) => return Err(e.into()),
+        }
+    }
+}
@@ -0,0 +1,171 @@
+use core::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use std::io;
+
+use openssl::{
+    error::ErrorStack,
+    hash::MessageDigest,
+    nid::Nid,
+    ssl::{Error as SslError, ErrorCode, ShutdownResult, SslConnector, SslMethod, SslRef, SslStream, SslVerifyMode},
+};
+use postgres_protocol::authentication::sasl::ChannelBinding;
+use xitca_io::io::{AsyncIo, Interest, Ready};
+
+use crate::{
+    config::{Config, SslMode},
+    error::{Error, TlsError},
+};
+
+/// A wrapper type for [SslStream].
+///
+/// This is to impl new trait for it.
+pub(crate) struct TlsStream<Io> {
+    io: SslStream<Io>,
+}
+
+#[cold]
+#[inline(never)]
+pub(crate) async fn connect<Io>(
+    io: Io,
+    host: &str,
+    cfg: &Config,
+) -> Result<(TlsStream<Io>, Option<ChannelBinding>), Error>
+where
+    Io: AsyncIo,
+{
+    let mode = cfg.get_ssl_mode();
+
+    let mut builder = SslConnector::builder(SslMethod::tls())?;
+
+    if let Some(path) = cfg.get_ssl_root_cert() {
+        builder.set_ca_file(path)?;
+    }
+
+    let verify_ca = match mode {
+        SslMode::VerifyCa | SslMode::VerifyFull => true,
+        SslMode::Require => cfg.get_ssl_root_cert().is_some(),
+        _ => false,
+    };
+
+    if !verify_ca {
+        builder.set_verify(SslVerifyMode::NONE);
+    }
+
+    let mut conf = builder.build().configure()?;
+    conf.set_verify_hostname(mode == SslMode::VerifyFull);
+
+    let ssl = conf.into_ssl(host)?;
+    let mut io = SslStream::new(ssl, io)?;
+    let mut interest = Interest::WRITABLE;
+
+    loop {
+        io.get_mut().ready(interest).await?;
+        match io.connect() {
+            Ok(_) => break,
+            Err(ref e) if e.code() == ErrorCode::WANT_READ => {
+                interest = Interest::READABLE;
+            }
+            Err(ref e) if e.code() == ErrorCode::WANT_WRITE => {
+                interest = Interest::WRITABLE;
+            }
+            Err(e) => return Err(e.into()),
+        }
+    }
+
+    let channel_binding = tls_server_end_point(io.ssl()).map(ChannelBinding::tls_server_end_point);
+
+    Ok((TlsStream { io }, channel_binding))
+}
+
+// hash server certificate with the hash function of it's signature algorithm.
+// md5 and sha1 are upgraded to sha256. see RFC 5929 section 4.1
+fn tls_server_end_point(ssl: &SslRef) -> Option<Vec<u8>> {
+    let cert = ssl.peer_certificate()?;
+    let algo_nid = cert.signature_algorithm().object().nid();
+    let signature_algorithms = algo_nid.signature_algorithms()?;
+    let md = match signature_algorithms.digest {
+        Nid::MD5 | Nid::SHA1 => MessageDigest::sha256(),
+        nid => MessageDigest::from_nid(nid)?,
+    };
+    cert.digest(md).ok().map(|digest| digest.to_vec())
+}
+
+impl From<ErrorStack> for Error {
+    fn from(e: ErrorStack) -> Self {
+        Self::Tls(TlsError::Backend(Box::new(e)))
+    }
+}
+
+impl From<SslError> for Error {
+    fn from(e: SslError) -> Self {
+        match e.into_io_error() {
+            Ok(e) => Self::Io(e),
+            Err(e) => Self::Tls(TlsError::Backend(Box::new(e))),
+        }
+    }
+}
+
+impl<Io: AsyncIo> AsyncIo for TlsStream<Io> {
+    type ReadyFuture<'f> = impl Future<Output = io::Result<Ready>> + 'f where Self: 'f;
+
+    #[inline]
+    fn ready(&self, interest: Interest) -> Self::ReadyFuture<'_> {
+        self.io.get_ref().ready(interest)
+    }
+
+    #[inline]
+    fn poll_ready(&self, interest: Interest, cx: &mut Context<'_>) -> Poll<io::Result<Ready>> {
+        self.io.get_ref().poll_ready(interest, cx)
+    }
+
+    fn is_vectored_write(&self) -> bool {
+        self.io.get_ref().is_vectored_write()
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        // copied from tokio-openssl crate.
+        match this.io.shutdown() {
+            Ok(ShutdownResult::Sent) | Ok(ShutdownResult::Received) => {}
+            Err(ref e) if e.code() == ErrorCode::ZERO_RETURN => {}
+            Err(ref e) if e.code() == ErrorCode::WANT_READ || e.code() == ErrorCode::WANT_WRITE => {
+                return Poll::Pending;
+            }
+            Err(e) => {
+                return Poll::Ready(Err(e
+                    .into_io_error()
+                    .unwrap_or_else(|e| io::Error::new(io::ErrorKind::Other, e))));
+            }
+        }
+
+        AsyncIo::poll_shutdown(Pin::new(this.io.get_mut()), cx)
+    }
+}
+
+impl<Io: AsyncIo> io::Read for TlsStream<Io> {
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        io::Read::read(&mut self.io, buf)
+    }
+}
+
+impl<Io: AsyncIo> io::Write for TlsStream<Io> {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        io::Write::write(&mut self.io, buf)
+    }
+
+    #[inline]
+    fn write_vectored(&mut self, bufs: &[io::IoSlice<'_>]) -> io::Result<usize> {
+        io::Write::write_vectored(&mut self.io, bufs)
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        io::Write::flush(&mut self.io)
+    }
+}
@@ -0,0 +1,283 @@
+use core::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use std::{fs::File, io, io::BufReader, sync::Arc, time::SystemTime};
+
+use postgres_protocol::authentication::sasl::ChannelBinding;
+use rustls::{
+    client::{ServerCertVerified, ServerCertVerifier, WebPkiVerifier},
+    Certificate, ClientConfig, ClientConnection, OwnedTrustAnchor, RootCertStore, ServerName, StreamOwned,
+};
+use sha2::{Digest, Sha256, Sha384, Sha512};
+use webpki_roots::TLS_SERVER_ROOTS;
+use xitca_io::io::{AsyncIo, Interest, Ready};
+
+use crate::{
+    config::{Config, SslMode},
+    error::{Error, TlsError},
+};
+
+/// A stream managed by rustls for tls read/write.
+pub(crate) struct TlsStream<Io>
+where
+    Io: AsyncIo,
+{
+    io: StreamOwned<ClientConnection, Io>,
+}
+
+#[cold]
+#[inline(never)]
+pub(crate) async fn connect<Io>(
+    mut io: Io,
+    host: &str,
+    cfg: &Config,
+) -> Result<(TlsStream<Io>, Option<ChannelBinding>), Error>
+where
+    Io: AsyncIo,
+{
+    let config = client_config(cfg)?;
+    let name = ServerName::try_from(host).map_err(|e| TlsError::Backend(Box::new(e)))?;
+    let mut conn = ClientConnection::new(Arc::new(config), name)?;
+
+    loop {
+        let interest = match conn.complete_io(&mut io) {
+            Ok(_) => break,
+            Err(ref e) if e.kind() == io::ErrorKind::WouldBlock => match (conn.wants_read(), conn.wants_write()) {
+                (true, true) => Interest::READABLE | Interest::WRITABLE,
+                (true, false) => Interest::READABLE,
+                (false, true) => Interest::WRITABLE,
+                (false, false) => unreachable!(),
+            },
+            Err(e) => return Err(e.into()),
+        };
+
+        io.ready(interest).await?;
+    }
+
+    let channel_binding = conn
+        .peer_certificates()
+        .and_then(|certs| certs.first())
+        .map(|cert| ChannelBinding::tls_server_end_point(tls_server_end_point(&cert.0)));
+
+    Ok((
+        TlsStream {
+            io: StreamOwned::new(conn, io),
+        },
+        channel_binding,
+    ))
+}
+
+fn client_config(cfg: &Config) -> Result<ClientConfig, Error> {
+    let certs = match cfg.get_ssl_root_cert() {
+        Some(path) => rustls_pemfile::certs(&mut BufReader::new(File::open(path)?))?,
+        None => Vec::new(),
+    };
+
+    let builder = ClientConfig::builder().with_safe_defaults();
+
+    let builder = match cfg.get_ssl_mode() {
+        SslMode::VerifyFull => {
+            let mut root_certs = RootCertStore::empty();
+            if certs.is_empty() {
+                root_certs.add_server_trust_anchors(TLS_SERVER_ROOTS.0.iter().map(|cert| {
+                    OwnedTrustAnchor::from_subject_spki_name_constraints(cert.subject, cert.spki, cert.name_constraints)
+                }));
+            } else {
+                root_certs.add_parsable_certificates(&certs);
+            }
+            builder.with_custom_certificate_verifier(Arc::new(WebPkiVerifier::new(root_certs, None)))
+        }
+        SslMode::VerifyCa => builder.with_custom_certificate_verifier(Arc::new(VerifyCa { certs })),
+        SslMode::Require if !certs.is_empty() => {
+            builder.with_custom_certificate_verifier(Arc::new(VerifyCa { certs }))
+        }
+        _ => builder.with_custom_certificate_verifier(Arc::new(NoVerify)),
+    };
+
+    Ok(builder.with_no_client_auth())
+}
+
+/// verifier skip all verification of server certificate.
+struct NoVerify;
+
+impl ServerCertVerifier for NoVerify {
+    fn verify_server_cert(
+        &self,
+        _: &Certificate,
+        _: &[Certificate],
+        _: &ServerName,
+        _: &mut dyn Iterator<Item = &[u8]>,
+        _: &[u8],
+        _: SystemTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        Ok(ServerCertVerified::assertion())
+    }
+}
+
+/// verifier check server certificate is signed by a trusted CA without checking it's host name.
+struct VerifyCa {
+    // DER encoded root certificates. bundled webpki roots are used when empty.
+    certs: Vec<Vec<u8>>,
+}
+
+impl ServerCertVerifier for VerifyCa {
+    fn verify_server_cert(
+        &self,
+        end_entity: &Certificate,
+        intermediates: &[Certificate],
+        _: &ServerName,
+        _: &mut dyn Iterator<Item = &[u8]>,
+        _: &[u8],
+        now: SystemTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        let anchors: Vec<_> = if self.certs.is_empty() {
+            TLS_SERVER_ROOTS
+                .0
+                .iter()
+                .map(|cert| webpki::TrustAnchor {
+                    subject: cert.subject,
+                    spki: cert.spki,
+                    name_constraints: cert.name_constraints,
+                })
+                .collect()
+        } else {
+            self.certs
+                .iter()
+                .filter_map(|cert| webpki::TrustAnchor::try_from_cert_der(cert).ok())
+                .collect()
+        };
+
+        let chain = intermediates.iter().map(|cert| cert.0.as_slice()).collect::<Vec<_>>();
+
+        let now = webpki::Time::try_from(now).map_err(|_| rustls::Error::FailedToGetCurrentTime)?;
+
+        webpki::EndEntityCert::try_from(end_entity.0.as_slice())
+            .and_then(|cert| {
+                cert.verify_is_valid_tls_server_cert(SIG_ALGS, &webpki::TlsServerTrustAnchors(&anchors), &chain, now)
+            })
+            .map(|_| ServerCertVerified::assertion())
+            .map_err(|e| rustls::Error::InvalidCertificateData(format!("invalid peer certificate: {e}")))
+    }
+}
+
+// copy/paste from rustls.
+static SIG_ALGS: &[&webpki::SignatureAlgorithm] = &[
+    &webpki::ECDSA_P256_SHA256,
+    &webpki::ECDSA_P256_SHA384,
+    &webpki::ECDSA_P384_SHA256,
+    &webpki::ECDSA_P384_SHA384,
+    &webpki::ED25519,
+    &webpki::RSA_PSS_2048_8192_SHA256_LEGACY_KEY,
+    &webpki::RSA_PSS_2048_8192_SHA384_LEGACY_KEY,
+    &webpki::RSA_PSS_2048_8192_SHA512_LEGACY_KEY,
+    &webpki::RSA_PKCS1_2048_8192_SHA256,
+    &webpki::RSA_PKCS1_2048_8192_SHA384,
+    &webpki::RSA_PKCS1_2048_8192_SHA512,
+    &webpki::RSA_PKCS1_3072_8192_SHA384,
+];
+
+// DER encoded object identifiers of signature algorithms using hash function other than sha256.
+const SHA384_WITH_RSA: &[u8] = &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x01, 0x0c];
+const SHA512_WITH_RSA: &[u8] = &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x01, 0x0d];
+const ECDSA_WITH_SHA384: &[u8] = &[0x2a, 0x86, 0x48, 0xce, 0x3d, 0x04, 0x03, 0x03];
+const ECDSA_WITH_SHA512: &[u8] = &[0x2a, 0x86, 0x48, 0xce, 0x3d, 0x04, 0x03, 0x04];
+
+// hash DER encoded server certificate with the hash function of it's signature algorithm.
+// md5 and sha1 are upgraded to sha256. see RFC 5929 section 4.1
+fn tls_server_end_point(cert: &[u8]) -> Vec<u8> {
+    match signature_algorithm(cert) {
+        Some(SHA384_WITH_RSA | ECDSA_WITH_SHA384) => Sha384::digest(cert).to_vec(),
+        Some(SHA512_WITH_RSA | ECDSA_WITH_SHA512) => Sha512::digest(cert).to_vec(),
+        _ => Sha256::digest(cert).to_vec(),
+    }
+}
+
+// extract object identifier of signatureAlgorithm field from DER encoded x509 certificate.
+fn signature_algorithm(cert: &[u8]) -> Option<&[u8]> {
+    const SEQUENCE: u8 = 0x30;
+    const OBJECT_IDENTIFIER: u8 = 0x06;
+
+    let (cert, _) = der_split(cert, SEQUENCE)?;
+    // skip tbsCertificate.
+    let (_, cert) = der_split(cert, SEQUENCE)?;
+    let (algorithm, _) = der_split(cert, SEQUENCE)?;
+    der_split(algorithm, OBJECT_IDENTIFIER).map(|(oid, _)| oid)
+}
+
+// split DER encoded tag-length-value with given tag into it's value and the remaining bytes.
+fn der_split(buf: &[u8], tag: u8) -> Option<(&[u8], &[u8])> {
+    let (&t, buf) = buf.split_first()?;
+    if t != tag {
+        return None;
+    }
+
+    let (&len, mut buf) = buf.split_first()?;
+    let len = if len & 0x80 == 0 {
+        len as usize
+    } else {
+        let n = (len & 0x7f) as usize;
+        if n > core::mem::size_of::<usize>() || buf.len() < n {
+            return None;
+        }
+        let (len, rest) = buf.split_at(n);
+        buf = rest;
+        len.iter().fold(0, |len, b| (len << 8) | *b as usize)
+    };
+
+    (buf.len() >= len).then(|| buf.split_at(len))
+}
+
+impl From<rustls::Error> for Error {
+    fn from(e: rustls::Error) -> Self {
+        Self::Tls(TlsError::Backend(Box::new(e)))
+    }
+}
+
+impl<Io: AsyncIo> AsyncIo for TlsStream<Io> {
+    type ReadyFuture<'f> = impl Future<Output = io::Result<Ready>> + 'f where Self: 'f;
+
+    #[inline]
+    fn ready(&self, interest: Interest) -> Self::ReadyFuture<'_> {
+        self.io.get_ref().ready(interest)
+    }
+
+    #[inline]
+    fn poll_ready(&self, interest: Interest, cx: &mut Context<'_>) -> Poll<io::Result<Ready>> {
+        self.io.get_ref().poll_ready(interest, cx)
+    }
+
+    fn is_vectored_write(&self) -> bool {
+        self.io.get_ref().is_vectored_write()
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        AsyncIo::poll_shutdown(Pin::new(self.get_mut().io.get_mut()), cx)
+    }
+}
+
+impl<Io: AsyncIo> io::Read for TlsStream<Io> {
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        io::Read::read(&mut self.io, buf)
+    }
+}
+
+impl<Io: AsyncIo> io::Write for TlsStream<Io> {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        io::Write::write(&mut self.io, buf)
+    }
+
+    #[inline]
+    fn write_vectored(&mut self, bufs: &[io::IoSlice<'_>]) -> io::Result<usize> {
+        io::Write::write_vectored(&mut self.io, bufs)
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        io::Write::flush(&mut self.io)
+    }
+}
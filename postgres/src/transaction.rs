@@ -0,0 +1,220 @@
+//! Transaction module is mostly copy/paste from `tokio_postgres::transaction`
+
+use core::ops::Deref;
+
+use super::{client::Client, error::Error};
+
+impl Client {
+    /// Begins a new database transaction.
+    ///
+    /// The transaction will roll back by default - use the [Transaction::commit] method to commit it.
+    pub async fn transaction(&self) -> Result<Transaction<'_>, Error> {
+        self.build_transaction().start().await
+    }
+
+    /// Returns a builder for a transaction with custom settings.
+    ///
+    /// Unlike the [Client::transaction] method, the builder can be used to control the transaction's isolation level
+    /// and other attributes.
+    pub fn build_transaction(&self) -> TransactionBuilder<'_> {
+        TransactionBuilder::new(self)
+    }
+}
+
+/// The isolation level of a database transaction.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum IsolationLevel {
+    /// Equivalent to `ReadCommitted`.
+    ReadUncommitted,
+    /// An individual statement in the transaction will see rows committed before it began.
+    ReadCommitted,
+    /// All statements in the transaction will see the same view of rows committed before the first query in the
+    /// transaction.
+    RepeatableRead,
+    /// The reads and writes in this transaction must be able to be committed as an atomic "unit" with respect to reads
+    /// and writes of all other concurrent serializable transactions without interleaving.
+    Serializable,
+}
+
+/// A builder for database transactions.
+pub struct TransactionBuilder<'a> {
+    client: &'a Client,
+    isolation_level: Option<IsolationLevel>,
+    read_only: Option<bool>,
+    deferrable: Option<bool>,
+}
+
+impl<'a> TransactionBuilder<'a> {
+    fn new(client: &'a Client) -> Self {
+        Self {
+            client,
+            isolation_level: None,
+            read_only: None,
+            deferrable: None,
+        }
+    }
+
+    /// Sets the isolation level of the transaction.
+    pub fn isolation_level(mut self, isolation_level: IsolationLevel) -> Self {
+        self.isolation_level = Some(isolation_level);
+        self
+    }
+
+    /// Sets the access mode of the transaction.
+    pub fn read_only(mut self, read_only: bool) -> Self {
+        self.read_only = Some(read_only);
+        self
+    }
+
+    /// Sets the deferrability of the transaction.
+    ///
+    /// If the transaction is also serializable and read only, creation of the transaction may block, but when it
+    /// completes the transaction is able to run with less overhead and a guarantee that it will not be aborted due to
+    /// serialization failure.
+    pub fn deferrable(mut self, deferrable: bool) -> Self {
+        self.deferrable = Some(deferrable);
+        self
+    }
+
+    /// Begins the transaction.
+    ///
+    /// The transaction will roll back by default - use the [Transaction::commit] method to commit it.
+    pub async fn start(self) -> Result<Transaction<'a>, Error> {
+        let mut query = String::from("START TRANSACTION");
+        let mut first = true;
+
+        if let Some(level) = self.isolation_level {
+            first = false;
+
+            query.push_str(" ISOLATION LEVEL ");
+            let level = match level {
+                IsolationLevel::ReadUncommitted => "READ UNCOMMITTED",
+                IsolationLevel::ReadCommitted => "READ COMMITTED",
+                IsolationLevel::RepeatableRead => "REPEATABLE READ",
+                IsolationLevel::Serializable => "SERIALIZABLE",
+            };
+            query.push_str(level);
+        }
+
+        if let Some(read_only) = self.read_only {
+            if !first {
+                query.push(',');
+            }
+            first = false;
+
+            let s = if read_only { " READ ONLY" } else { " READ WRITE" };
+            query.push_str(s);
+        }
+
+        if let Some(deferrable) = self.deferrable {
+            if !first {
+                query.push(',');
+            }
+
+            let s = if deferrable { " DEFERRABLE" } else { " NOT DEFERRABLE" };
+            query.push_str(s);
+        }
+
+        self.client.execute_simple(&query).await?;
+
+        Ok(Transaction::new(self.client, None))
+    }
+}
+
+/// A representation of a PostgreSQL database transaction.
+///
+/// Transactions will implicitly roll back when dropped. Use the [Transaction::commit] method to commit the changes made
+/// in the transaction. Transactions can be nested, with inner transactions implemented via savepoints.
+///
+/// [Client] methods are accessible from transaction through [Deref].
+pub struct Transaction<'a> {
+    client: &'a Client,
+    savepoint: Option<Savepoint>,
+    done: bool,
+}
+
+/// A representation of a PostgreSQL database savepoint.
+struct Savepoint {
+    name: String,
+    depth: u32,
+}
+
+impl Deref for Transaction<'_> {
+    type Target = Client;
+
+    fn deref(&self) -> &Self::Target {
+        self.client
+    }
+}
+
+impl Drop for Transaction<'_> {
+    fn drop(&mut self) {
+        if self.done || self.client.closed() {
+            return;
+        }
+
+        let query = match self.savepoint {
+            Some(ref sp) => format!("ROLLBACK TO {}", sp.name),
+            None => String::from("ROLLBACK"),
+        };
+
+        // response is not concerned when transaction is dropped. the database would still send
+        // it and the dropped receiver would make the response ignored silently.
+        let _res = self.client.simple(&query);
+    }
+}
+
+impl<'a> Transaction<'a> {
+    fn new(client: &'a Client, savepoint: Option<Savepoint>) -> Self {
+        Self {
+            client,
+            savepoint,
+            done: false,
+        }
+    }
+
+    /// Consumes the transaction, committing all changes made within it.
+    pub async fn commit(mut self) -> Result<(), Error> {
+        self.done = true;
+        let query = match self.savepoint {
+            Some(ref sp) => format!("RELEASE {}", sp.name),
+            None => String::from("COMMIT"),
+        };
+        self.client.execute_simple(&query).await.map(|_| ())
+    }
+
+    /// Rolls the transaction back, discarding all changes made within it.
+    ///
+    /// This is equivalent to [Transaction]'s [Drop] implementation, but provides any error encountered to the caller.
+    pub async fn rollback(mut self) -> Result<(), Error> {
+        self.done = true;
+        let query = match self.savepoint {
+            Some(ref sp) => format!("ROLLBACK TO {}", sp.name),
+            None => String::from("ROLLBACK"),
+        };
+        self.client.execute_simple(&query).await.map(|_| ())
+    }
+
+    /// Like [Client::transaction], but creates a nested transaction via a savepoint.
+    pub async fn transaction(&mut self) -> Result<Transaction<'_>, Error> {
+        self._savepoint(None).await
+    }
+
+    /// Like [Client::transaction], but creates a nested transaction via a savepoint with the specified name.
+    pub async fn savepoint<I>(&mut self, name: I) -> Result<Transaction<'_>, Error>
+    where
+        I: Into<String>,
+    {
+        self._savepoint(Some(name.into())).await
+    }
+
+    async fn _savepoint(&mut self, name: Option<String>) -> Result<Transaction<'_>, Error> {
+        let depth = self.savepoint.as_ref().map_or(0, |sp| sp.depth) + 1;
+        let name = name.unwrap_or_else(|| format!("sp_{depth}"));
+        let query = format!("SAVEPOINT {name}");
+        self.client.execute_simple(&query).await?;
+
+        Ok(Transaction::new(self.client, Some(Savepoint { name, depth })))
+    }
+}
@@ -0,0 +1,37 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+
+// constrained routes sharing the same routing tree key with plain and static routes.
+const ROUTES: &[&str] = &[
+    "/users/me",
+    "/users/{id:int}",
+    "/users/{slug:[a-z-]+}",
+    "/users/{name}/posts/{post:uint}",
+    "/files/{file}.{ext:[a-z]+}",
+    "/files/{name}",
+    "/v{major:uint}.{minor:uint}/{*path}",
+];
+
+fuzz_target!(|data: (Vec<String>, String)| {
+    let mut router = xitca_router::Router::new();
+
+    for route in ROUTES {
+        router.insert(*route, *route).unwrap();
+    }
+
+    // arbitrary routes must either be rejected or inserted without breaking existing ones.
+    for route in data.0 {
+        let _ = router.insert(route, "");
+    }
+
+    if let Ok(matched) = router.at(&data.1) {
+        if matched.value.is_empty() {
+            return;
+        }
+
+        for (key, value) in matched.params.iter() {
+            assert!(!key.is_empty());
+            assert!(!value.is_empty());
+        }
+    }
+});
@@ -19,6 +19,10 @@ pub enum InsertError {
     UnnamedParam,
     /// Catch-all parameters are only allowed at the end of a path.
     InvalidCatchAll,
+    /// Attempted to insert a route with a name that is already registered.
+    DuplicateName(String),
+    /// Brace style parameter is malformed or has an unknown constraint.
+    InvalidParam(String),
 }
 
 impl fmt::Display for InsertError {
@@ -34,6 +38,8 @@ impl fmt::Display for InsertError {
             Self::TooManyParams => f.write_str("only one parameter is allowed per path segment"),
             Self::UnnamedParam => f.write_str("parameters must be registered with a name"),
             Self::InvalidCatchAll => f.write_str("catch-all parameters are only allowed at the end of a route"),
+            Self::DuplicateName(ref name) => write!(f, "route name: {name} is already registered"),
+            Self::InvalidParam(ref segment) => write!(f, "invalid parameter in route segment: {segment}"),
         }
     }
 }
@@ -100,3 +106,24 @@ impl fmt::Display for MatchError {
 }
 
 impl std::error::Error for MatchError {}
+
+/// Represents errors that can occur when generating URL path from a named route.
+#[non_exhaustive]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum UrlError {
+    /// No route is registered with the given name.
+    NotFound,
+    /// Value of the parameter is not provided.
+    MissingParam(String),
+}
+
+impl fmt::Display for UrlError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotFound => f.write_str("url error: route name not found"),
+            Self::MissingParam(ref key) => write!(f, "url error: missing value of parameter: {key}"),
+        }
+    }
+}
+
+impl std::error::Error for UrlError {}
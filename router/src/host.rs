@@ -0,0 +1,192 @@
+use alloc::{string::String, vec::Vec};
+
+use xitca_unsafe_collection::bytes::BytesStr;
+
+use super::{params::Params, InsertError, Match, MatchError};
+
+/// A router matching host name of request's authority.
+///
+/// Host pattern is made of labels separated by `.`. A label can be:
+/// - static text matched case-insensitively. e.g. `example.com`
+/// - a named parameter capturing exactly one label. e.g. `{tenant}.example.com`
+/// - a leading `*` matching one or more labels without capturing. e.g. `*.example.com`
+///
+/// Exact host is matched first, then patterns with fewer parameters and wildcard patterns last.
+///
+/// ```rust
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut router = xitca_router::HostRouter::new();
+/// router.insert("api.example.com", "api")?;
+/// router.insert("{tenant}.example.com", "tenant")?;
+/// router.insert("*.example.com", "wildcard")?;
+///
+/// assert_eq!(*router.at("API.example.com:8080")?.value, "api");
+///
+/// let matched = router.at("foo.example.com")?;
+/// assert_eq!(*matched.value, "tenant");
+/// assert_eq!(matched.params.get("tenant"), Some("foo"));
+///
+/// assert_eq!(*router.at("a.b.example.com")?.value, "wildcard");
+/// assert!(router.at("example.com").is_err());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct HostRouter<T> {
+    hosts: Vec<(Host, T)>,
+}
+
+#[derive(Clone)]
+struct Host {
+    pattern: String,
+    // labels in reverse order. wildcard label is not included.
+    labels: Vec<Label>,
+    wildcard: bool,
+}
+
+#[derive(Clone)]
+enum Label {
+    Static(String),
+    Param(BytesStr),
+}
+
+impl<T> Default for HostRouter<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> HostRouter<T> {
+    /// Construct a new host router.
+    pub const fn new() -> Self {
+        Self { hosts: Vec::new() }
+    }
+
+    /// Returns `true` if there is no host inserted.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.hosts.is_empty()
+    }
+
+    /// Insert a host pattern.
+    pub fn insert(&mut self, host: impl Into<String>, value: T) -> Result<(), InsertError> {
+        let host = Host::parse(host.into())?;
+
+        if let Some((h, _)) = self.hosts.iter().find(|(h, _)| h.is_ambiguous(&host)) {
+            return Err(InsertError::Conflict {
+                with: h.pattern.clone(),
+            });
+        }
+
+        let priority = host.priority();
+        let i = self
+            .hosts
+            .iter()
+            .position(|(h, _)| h.priority() > priority)
+            .unwrap_or(self.hosts.len());
+        self.hosts.insert(i, (host, value));
+
+        Ok(())
+    }
+
+    /// Tries to find a value in the router matching the given host. Port and trailing dot of the
+    /// host are ignored.
+    pub fn at(&self, host: &str) -> Result<Match<&T>, MatchError> {
+        let host = strip_port(host);
+        let host = host.strip_suffix('.').unwrap_or(host);
+
+        self.hosts
+            .iter()
+            .find_map(|(h, value)| h.matches(host).map(|params| Match { value, params }))
+            .ok_or(MatchError::NotFound)
+    }
+}
+
+impl Host {
+    fn parse(pattern: String) -> Result<Self, InsertError> {
+        let invalid = || InsertError::InvalidParam(pattern.clone());
+
+        let (rest, wildcard) = match pattern.strip_prefix("*.") {
+            Some(rest) => (rest, true),
+            None => (pattern.as_str(), false),
+        };
+
+        let mut labels = Vec::new();
+
+        for label in rest.rsplit('.') {
+            let label = match label.strip_prefix('{') {
+                Some(name) => {
+                    let name = name.strip_suffix('}').ok_or_else(invalid)?;
+                    if name.is_empty() {
+                        return Err(InsertError::UnnamedParam);
+                    }
+                    if name.contains(['{', '}', '*']) {
+                        return Err(invalid());
+                    }
+                    Label::Param(name.into())
+                }
+                None if label.is_empty() || label.contains(['{', '}', '*', ':']) => return Err(invalid()),
+                None => Label::Static(label.to_ascii_lowercase()),
+            };
+            labels.push(label);
+        }
+
+        Ok(Self {
+            pattern,
+            labels,
+            wildcard,
+        })
+    }
+
+    // lower value is matched first.
+    fn priority(&self) -> (bool, usize, usize) {
+        let params = self.labels.iter().filter(|l| matches!(l, Label::Param(_))).count();
+        (self.wildcard, params, usize::MAX - self.labels.len())
+    }
+
+    fn is_ambiguous(&self, other: &Self) -> bool {
+        self.wildcard == other.wildcard
+            && self.labels.len() == other.labels.len()
+            && self.labels.iter().zip(other.labels.iter()).all(|(a, b)| match (a, b) {
+                (Label::Static(a), Label::Static(b)) => a == b,
+                (Label::Param(_), Label::Param(_)) => true,
+                _ => false,
+            })
+    }
+
+    fn matches(&self, host: &str) -> Option<Params> {
+        let mut params = Params::new();
+        let mut labels = host.rsplit('.');
+
+        for label in self.labels.iter() {
+            let value = labels.next().filter(|value| !value.is_empty())?;
+            match label {
+                Label::Static(s) => {
+                    if !s.eq_ignore_ascii_case(value) {
+                        return None;
+                    }
+                }
+                Label::Param(name) => params.push(name.clone(), value),
+            }
+        }
+
+        // wildcard matches one or more remaining labels.
+        match labels.next() {
+            Some(label) if self.wildcard && !label.is_empty() => Some(params),
+            None if !self.wildcard => Some(params),
+            _ => None,
+        }
+    }
+}
+
+fn strip_port(host: &str) -> &str {
+    // ipv6 literal. e.g. [::1]:8080
+    if host.starts_with('[') {
+        return host.find(']').map(|i| &host[..=i]).unwrap_or(host);
+    }
+
+    match host.rsplit_once(':') {
+        Some((host, port)) if port.bytes().all(|b| b.is_ascii_digit()) => host,
+        _ => host,
+    }
+}
@@ -77,10 +77,15 @@
 extern crate alloc;
 
 mod error;
+mod host;
+mod pattern;
 mod router;
 mod tree;
+mod urls;
 
 pub mod params;
 
-pub use error::{InsertError, MatchError};
+pub use error::{InsertError, MatchError, UrlError};
+pub use host::HostRouter;
 pub use router::{Match, Router};
+pub use urls::Urls;
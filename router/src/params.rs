@@ -110,6 +110,16 @@ impl IntoIterator for Params {
     }
 }
 
+impl Extend<(BytesStr, SmallBoxedStr)> for Params {
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = (BytesStr, SmallBoxedStr)>,
+    {
+        self.inner
+            .extend(iter.into_iter().map(|(key, value)| Param { key, value }));
+    }
+}
+
 pub struct Iter<'a> {
     inner: slice::Iter<'a, Param>,
 }
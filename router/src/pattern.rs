@@ -0,0 +1,353 @@
+use alloc::{string::String, vec::Vec};
+
+use xitca_unsafe_collection::bytes::BytesStr;
+
+use super::{params::Params, InsertError};
+
+// brace style route pattern. e.g. `/users/{id:int}`, `/posts/{slug:[a-z-]+}`, `/files/{file}.{ext}`.
+//
+// every path segment containing parameter is inserted into routing tree as a positional wildcard
+// (`:0`, `:1`, `*2` etc) and the pattern is checked against the matched values afterwards.
+#[derive(Clone)]
+#[cfg_attr(test, derive(Debug))]
+pub(crate) struct Pattern {
+    segments: Vec<Segment>,
+}
+
+// parts of a path segment after it's static prefix. the first part is always a parameter and
+// parameters are always separated by static parts.
+#[derive(Clone)]
+#[cfg_attr(test, derive(Debug))]
+struct Segment {
+    parts: Vec<Part>,
+}
+
+#[derive(Clone)]
+#[cfg_attr(test, derive(Debug))]
+enum Part {
+    Param {
+        name: BytesStr,
+        constraint: Option<Constraint>,
+    },
+    Static(String),
+}
+
+#[derive(Clone, PartialEq, Eq)]
+#[cfg_attr(test, derive(Debug))]
+enum Constraint {
+    // `int`: decimal digits with optional leading `-`.
+    Int,
+    // `uint`: decimal digits.
+    Uint,
+    // `[a-z_]`, `[^.]+`: char class matching one char or one or more chars with `+` suffix.
+    Class {
+        ranges: Vec<(char, char)>,
+        negated: bool,
+        repeat: bool,
+    },
+}
+
+impl Pattern {
+    // parse route into key of routing tree and it's pattern. returns None when route does not
+    // use brace syntax and can be inserted into routing tree as is.
+    pub(crate) fn parse(route: &str) -> Result<Option<(String, Self)>, InsertError> {
+        if !route.contains('{') {
+            return Ok(None);
+        }
+
+        let mut key = String::with_capacity(route.len());
+        let mut segments = Vec::new();
+
+        for (i, segment) in route.split('/').enumerate() {
+            if i > 0 {
+                key.push('/');
+            }
+
+            let (prefix, parsed) = parse_segment(segment)?;
+            key.push_str(prefix);
+
+            if let Some((parts, catch_all)) = parsed {
+                key.push(if catch_all { '*' } else { ':' });
+                key.push_str(&segments.len().to_string());
+                segments.push(Segment { parts });
+            }
+        }
+
+        Ok(Some((key, Self { segments })))
+    }
+
+    // patterns accepting the same paths regardless of their parameter names.
+    pub(crate) fn is_ambiguous(&self, other: &Self) -> bool {
+        self.segments.len() == other.segments.len()
+            && self.segments.iter().zip(other.segments.iter()).all(|(a, b)| {
+                a.parts.len() == b.parts.len()
+                    && a.parts.iter().zip(b.parts.iter()).all(|(a, b)| match (a, b) {
+                        (Part::Param { constraint: a, .. }, Part::Param { constraint: b, .. }) => a == b,
+                        (Part::Static(a), Part::Static(b)) => a == b,
+                        _ => false,
+                    })
+            })
+    }
+
+    // count of static parts and constrained parameters. more specific pattern is tried first
+    // when multiple patterns share the same key.
+    pub(crate) fn specificity(&self) -> usize {
+        self.segments
+            .iter()
+            .flat_map(|segment| segment.parts.iter())
+            .filter(|part| !matches!(part, Part::Param { constraint: None, .. }))
+            .count()
+    }
+
+    // check params matched by routing tree against the pattern. returns params with their real
+    // names on success.
+    pub(crate) fn matches(&self, params: &Params) -> Option<Params> {
+        let mut values = Vec::new();
+
+        for (segment, (_, value)) in self.segments.iter().zip(params.iter()) {
+            let start = values.len();
+            segment.split(value, &mut values)?;
+            values[start..].reverse();
+        }
+
+        let mut params = Params::new();
+        for (name, value) in values {
+            params.push(name.clone(), value);
+        }
+
+        Some(params)
+    }
+}
+
+impl Segment {
+    // split value from right to left so every parameter except the last one is greedy.
+    // e.g. `{file}.{ext}` matches `archive.tar.gz` with `file` being `archive.tar`.
+    fn split<'s, 'v>(&'s self, mut value: &'v str, values: &mut Vec<(&'s BytesStr, &'v str)>) -> Option<()> {
+        let mut parts = self.parts.iter().rev().peekable();
+
+        while let Some(part) = parts.next() {
+            match part {
+                Part::Static(s) => value = value.strip_suffix(s.as_str())?,
+                Part::Param { name, constraint } => {
+                    let v = match parts.peek() {
+                        Some(Part::Static(sep)) => {
+                            let i = value.rfind(sep.as_str())? + sep.len();
+                            let (rest, v) = value.split_at(i);
+                            value = rest;
+                            v
+                        }
+                        _ => core::mem::take(&mut value),
+                    };
+
+                    if v.is_empty() || matches!(constraint, Some(c) if !c.check(v)) {
+                        return None;
+                    }
+
+                    values.push((name, v));
+                }
+            }
+        }
+
+        value.is_empty().then_some(())
+    }
+}
+
+impl Constraint {
+    fn parse(s: &str) -> Option<Self> {
+        match s {
+            "int" => Some(Self::Int),
+            "uint" => Some(Self::Uint),
+            _ => {
+                let s = s.strip_prefix('[')?;
+                let (class, repeat) = match s.strip_suffix("]+") {
+                    Some(class) => (class, true),
+                    None => (s.strip_suffix(']')?, false),
+                };
+                let (class, negated) = match class.strip_prefix('^') {
+                    Some(class) => (class, true),
+                    None => (class, false),
+                };
+
+                let mut ranges = Vec::new();
+                let mut chars = class.chars();
+
+                while let Some(start) = chars.next() {
+                    // '-' at the start or end of class is a literal char.
+                    let mut ahead = chars.clone();
+                    if let (Some('-'), Some(end)) = (ahead.next(), ahead.next()) {
+                        if end < start {
+                            return None;
+                        }
+                        ranges.push((start, end));
+                        chars = ahead;
+                    } else {
+                        ranges.push((start, start));
+                    }
+                }
+
+                (!ranges.is_empty()).then_some(Self::Class {
+                    ranges,
+                    negated,
+                    repeat,
+                })
+            }
+        }
+    }
+
+    fn check(&self, value: &str) -> bool {
+        fn digits(value: &str) -> bool {
+            !value.is_empty() && value.bytes().all(|b| b.is_ascii_digit())
+        }
+
+        match *self {
+            Self::Int => digits(value.strip_prefix('-').unwrap_or(value)),
+            Self::Uint => digits(value),
+            Self::Class {
+                ref ranges,
+                negated,
+                repeat,
+            } => {
+                let is_match = |c: char| ranges.iter().any(|&(start, end)| start <= c && c <= end) != negated;
+                let mut chars = value.chars();
+                if repeat {
+                    chars.all(is_match)
+                } else {
+                    matches!(chars.next(), Some(c) if is_match(c)) && chars.next().is_none()
+                }
+            }
+        }
+    }
+}
+
+// parameter parts of a segment and if it's a catch-all parameter.
+type ParamParts = (Vec<Part>, bool);
+
+// parse a path segment into it's static prefix and optional parameter parts. plain `:name` and
+// `*name` syntax is accepted in brace style route as parameter without constraint.
+fn parse_segment(segment: &str) -> Result<(&str, Option<ParamParts>), InsertError> {
+    let invalid = || InsertError::InvalidParam(segment.into());
+
+    let Some(start) = segment.find('{') else {
+        if segment.contains('}') {
+            return Err(invalid());
+        }
+
+        return match segment.find([':', '*']) {
+            Some(i) => {
+                let (prefix, name) = (&segment[..i], &segment[i + 1..]);
+                if name.is_empty() {
+                    return Err(InsertError::UnnamedParam);
+                }
+                let parts = vec![Part::Param {
+                    name: name.into(),
+                    constraint: None,
+                }];
+                Ok((prefix, Some((parts, segment.as_bytes()[i] == b'*'))))
+            }
+            None => Ok((segment, None)),
+        };
+    };
+
+    let (prefix, mut rest) = segment.split_at(start);
+    if prefix.contains([':', '*', '}']) {
+        return Err(invalid());
+    }
+
+    let mut parts = Vec::new();
+    let mut catch_all = false;
+
+    while !rest.is_empty() {
+        match rest.strip_prefix('{') {
+            Some(param) => {
+                let end = param.find('}').ok_or_else(invalid)?;
+                rest = &param[end + 1..];
+
+                if matches!(parts.last(), Some(Part::Param { .. })) {
+                    return Err(InsertError::TooManyParams);
+                }
+
+                let (name, constraint) = match param[..end].split_once(':') {
+                    Some((name, constraint)) => (name, Some(Constraint::parse(constraint).ok_or_else(invalid)?)),
+                    None => (&param[..end], None),
+                };
+
+                let name = match name.strip_prefix('*') {
+                    Some(name) => {
+                        catch_all = true;
+                        name
+                    }
+                    None => name,
+                };
+
+                if name.is_empty() {
+                    return Err(InsertError::UnnamedParam);
+                }
+
+                if name.contains(['{', '*']) {
+                    return Err(invalid());
+                }
+
+                parts.push(Part::Param {
+                    name: name.into(),
+                    constraint,
+                });
+            }
+            None => {
+                let end = rest.find('{').unwrap_or(rest.len());
+                let (s, tail) = rest.split_at(end);
+                if s.contains([':', '*', '}']) {
+                    return Err(invalid());
+                }
+                parts.push(Part::Static(s.into()));
+                rest = tail;
+            }
+        }
+    }
+
+    // catch-all parameter takes the whole segment.
+    if catch_all && parts.len() > 1 {
+        return Err(InsertError::InvalidCatchAll);
+    }
+
+    Ok((prefix, Some((parts, catch_all))))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let (key, pattern) = Pattern::parse("/users/{id:int}/files/v{major}.{minor}/{*path}")
+            .unwrap()
+            .unwrap();
+        assert_eq!(key, "/users/:0/files/v:1/*2");
+        assert_eq!(pattern.segments.len(), 3);
+        assert_eq!(pattern.segments[1].parts.len(), 3);
+        assert_eq!(pattern.specificity(), 2);
+
+        assert!(Pattern::parse("/users/:id").unwrap().is_none());
+        assert_eq!(
+            Pattern::parse("/{id:float}").unwrap_err(),
+            InsertError::InvalidParam("{id:float}".into())
+        );
+        assert_eq!(Pattern::parse("/{a}{b}").unwrap_err(), InsertError::TooManyParams);
+        assert_eq!(Pattern::parse("/{}").unwrap_err(), InsertError::UnnamedParam);
+        assert_eq!(Pattern::parse("/{*a}.txt").unwrap_err(), InsertError::InvalidCatchAll);
+    }
+
+    #[test]
+    fn constraint() {
+        let c = Constraint::parse("int").unwrap();
+        assert!(c.check("-12") && c.check("0") && !c.check("-") && !c.check("1a"));
+
+        let c = Constraint::parse("[a-z-]+").unwrap();
+        assert!(c.check("hello-world") && !c.check("Hello"));
+
+        let c = Constraint::parse("[^.]").unwrap();
+        assert!(c.check("a") && !c.check(".") && !c.check("ab"));
+
+        assert!(Constraint::parse("[z-a]").is_none());
+        assert!(Constraint::parse("[]").is_none());
+    }
+}
@@ -1,4 +1,6 @@
-use super::{params::Params, tree::Node, InsertError, MatchError};
+use core::mem;
+
+use super::{params::Params, pattern::Pattern, tree::Node, urls::Urls, InsertError, MatchError};
 
 /// A URL router.
 ///
@@ -6,13 +8,71 @@ use super::{params::Params, tree::Node, InsertError, MatchError};
 #[derive(Clone)]
 #[cfg_attr(test, derive(Debug))]
 pub struct Router<T> {
-    root: Node<T>,
+    root: Node<Entry<T>>,
+    urls: Urls,
+}
+
+// value stored in routing tree.
+#[derive(Clone)]
+#[cfg_attr(test, derive(Debug))]
+enum Entry<T> {
+    Plain(T),
+    // brace style routes sharing the same key in routing tree. ordered by their matching priority.
+    Patterns(Vec<Candidate<T>>),
+}
+
+#[derive(Clone)]
+#[cfg_attr(test, derive(Debug))]
+struct Candidate<T> {
+    route: String,
+    pattern: Pattern,
+    value: T,
+}
+
+impl<T> Entry<T> {
+    fn push(&mut self, key: &str, candidate: Candidate<T>) -> Result<(), InsertError> {
+        let Entry::Patterns(candidates) = self else {
+            return Err(InsertError::Conflict { with: key.into() });
+        };
+
+        if let Some(c) = candidates.iter().find(|c| c.pattern.is_ambiguous(&candidate.pattern)) {
+            return Err(InsertError::Conflict { with: c.route.clone() });
+        }
+
+        let specificity = candidate.pattern.specificity();
+        let i = candidates
+            .iter()
+            .position(|c| c.pattern.specificity() < specificity)
+            .unwrap_or(candidates.len());
+        candidates.insert(i, candidate);
+
+        Ok(())
+    }
+
+    fn find(&self, params: &Params) -> Option<(&T, Option<Params>)> {
+        match self {
+            Entry::Plain(value) => Some((value, None)),
+            Entry::Patterns(candidates) => candidates
+                .iter()
+                .find_map(|c| c.pattern.matches(params).map(|params| (&c.value, Some(params)))),
+        }
+    }
+
+    fn into_value(self) -> T {
+        match self {
+            Entry::Plain(value) => value,
+            Entry::Patterns(candidates) => candidates.into_iter().next().expect("empty route candidates").value,
+        }
+    }
 }
 
 impl<T> Router<T> {
     /// Construct a new router.
     pub const fn new() -> Self {
-        Self { root: Node::new() }
+        Self {
+            root: Node::new(),
+            urls: Urls::new(),
+        }
     }
 
     /// Insert a route.
@@ -28,8 +88,171 @@ impl<T> Router<T> {
     /// # Ok(())
     /// # }
     /// ```
+    ///
+    /// Parameters can also be written in brace syntax with optional constraint. A path failing the
+    /// constraints falls through to the next matching route.
+    ///
+    /// - `{id:int}` and `{id:uint}` match signed and unsigned decimal digits.
+    /// - `{slug:[a-z-]+}` matches one or more chars of the class. Without `+` suffix the class matches
+    ///   exactly one char and `[^...]` negates the class.
+    /// - `{file}.{ext}` matches multiple parameters in one segment separated by static text. Every
+    ///   parameter except the last one is greedy.
+    /// - `{*path}` is a catch-all parameter.
+    ///
+    /// ```rust
+    /// # use xitca_router::Router;
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut router = Router::new();
+    /// router.insert("/users/{id:int}", "A User")?;
+    /// router.insert("/users/{name}", "A Named User")?;
+    /// router.insert("/files/{file}.{ext:[a-z]+}", "A File")?;
+    ///
+    /// assert_eq!(*router.at("/users/1")?.value, "A User");
+    /// assert_eq!(*router.at("/users/foo")?.value, "A Named User");
+    ///
+    /// let matched = router.at("/files/archive.tar.gz")?;
+    /// assert_eq!(matched.params.get("file"), Some("archive.tar"));
+    /// assert_eq!(matched.params.get("ext"), Some("gz"));
+    /// # Ok(())
+    /// # }
+    /// ```
     pub fn insert(&mut self, route: impl Into<String>, value: T) -> Result<(), InsertError> {
-        self.root.insert(route, value)
+        let route = route.into();
+        match Pattern::parse(&route)? {
+            None => self.root.insert(route, Entry::Plain(value)),
+            Some((key, pattern)) => {
+                let candidate = Candidate { route, pattern, value };
+                match self.root.route_mut(&key) {
+                    Some(entry) => entry.push(&key, candidate),
+                    None => self.root.insert(key, Entry::Patterns(vec![candidate])),
+                }
+            }
+        }
+    }
+
+    /// Insert a route with a name. The name can be used to generate URL path of the route with [Urls].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use xitca_router::Router;
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut router = Router::new();
+    /// router.insert_named("user", "/users/:id", "A User")?;
+    ///
+    /// assert_eq!(router.urls().url_for("user", [("id", "1")])?, "/users/1");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn insert_named(
+        &mut self,
+        name: impl Into<String>,
+        route: impl Into<String>,
+        value: T,
+    ) -> Result<(), InsertError> {
+        let name = name.into();
+        if self.urls.route(&name).is_some() {
+            return Err(InsertError::DuplicateName(name));
+        }
+
+        let route = route.into();
+        self.insert(route.clone(), value)?;
+        self.urls.insert(name, route);
+
+        Ok(())
+    }
+
+    /// Insert a route or replace the value of existing route that is inserted with the exact same
+    /// route string. Returns the replaced value.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use xitca_router::Router;
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut router = Router::new();
+    /// router.insert("/users/:id", "A User")?;
+    ///
+    /// assert_eq!(router.replace("/users/:id", "Another User")?, Some("A User"));
+    /// assert_eq!(*router.at("/users/1")?.value, "Another User");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn replace(&mut self, route: impl Into<String>, value: T) -> Result<Option<T>, InsertError> {
+        let route = route.into();
+        match self.value_mut(&route) {
+            Some(v) => Ok(Some(mem::replace(v, value))),
+            None => self.insert(route, value).map(|_| None),
+        }
+    }
+
+    /// Remove the route that is inserted with the exact same route string. Returns the value of
+    /// removed route.
+    ///
+    /// Removal rebuilds the routing tree and is not meant for hot path.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use xitca_router::Router;
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut router = Router::new();
+    /// router.insert("/home", "Welcome!")?;
+    /// router.insert("/users/:id", "A User")?;
+    ///
+    /// assert_eq!(router.remove("/users/:id"), Some("A User"));
+    /// assert!(router.at("/users/1").is_err());
+    /// assert!(router.at("/home").is_ok());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn remove(&mut self, route: &str) -> Option<T> {
+        let key = match Pattern::parse(route).ok()? {
+            Some((key, _)) => {
+                let Entry::Patterns(candidates) = self.root.route_mut(&key)? else {
+                    return None;
+                };
+                let i = candidates.iter().position(|c| c.route == route)?;
+
+                // other routes share the same key and routing tree is left untouched.
+                if candidates.len() > 1 {
+                    self.urls.remove_route(route);
+                    return Some(candidates.remove(i).value);
+                }
+
+                key
+            }
+            None => {
+                let Entry::Plain(_) = self.root.route_mut(route)? else {
+                    return None;
+                };
+                route.into()
+            }
+        };
+
+        let mut routes = Vec::new();
+        mem::take(&mut self.root).into_routes(&mut Vec::new(), &mut routes);
+
+        let mut removed = None;
+        for (path, entry) in routes {
+            if removed.is_none() && path == key {
+                removed = Some(entry.into_value());
+            } else {
+                self.root
+                    .insert(path, entry)
+                    .expect("re-insert of existing route must not fail");
+            }
+        }
+
+        self.urls.remove_route(route);
+
+        removed
+    }
+
+    /// Named routes of the router.
+    #[inline]
+    pub fn urls(&self) -> &Urls {
+        &self.urls
     }
 
     /// Tries to find a value in the router matching the given path.
@@ -49,7 +272,26 @@ impl<T> Router<T> {
     /// ```
     #[inline]
     pub fn at(&self, path: &str) -> Result<Match<&T>, MatchError> {
-        self.root.at(path).map(|(value, params)| Match { value, params })
+        self.root
+            .at_with(path, |entry, params| entry.find(params))
+            .map(|((value, matched), params)| Match {
+                value,
+                params: matched.unwrap_or(params),
+            })
+    }
+
+    // find the value inserted with the exact same route.
+    fn value_mut(&mut self, route: &str) -> Option<&mut T> {
+        match Pattern::parse(route).ok()? {
+            Some((key, _)) => match self.root.route_mut(&key)? {
+                Entry::Patterns(candidates) => candidates.iter_mut().find(|c| c.route == route).map(|c| &mut c.value),
+                Entry::Plain(_) => None,
+            },
+            None => match self.root.route_mut(route)? {
+                Entry::Plain(value) => Some(value),
+                Entry::Patterns(_) => None,
+            },
+        }
     }
 
     #[cfg(feature = "test_helpers")]
@@ -160,6 +160,46 @@ impl<T> Node<T> {
         }
     }
 
+    // find the value inserted with the exact same route.
+    pub fn route_mut(&mut self, route: &str) -> Option<&mut T> {
+        let mut current = self;
+        let mut route = route.as_bytes();
+
+        loop {
+            route = route.strip_prefix(current.prefix.as_bytes())?;
+
+            // param name must be followed by '/' or the end of route. e.g. :id does not match :identity
+            if current.node_type == NodeType::Param && matches!(route.first(), Some(b) if *b != b'/') {
+                return None;
+            }
+
+            if route.is_empty() {
+                return current.value.as_mut();
+            }
+
+            current = current
+                .children
+                .iter_mut()
+                .find(|child| route.starts_with(child.prefix.as_bytes()))?;
+        }
+    }
+
+    // destruct node into a list of routes and their values.
+    pub fn into_routes(self, route: &mut Vec<u8>, routes: &mut Vec<(String, T)>) {
+        let len = route.len();
+        route.extend_from_slice(self.prefix.as_bytes());
+
+        if let Some(value) = self.value {
+            routes.push((String::from_utf8(route.clone()).unwrap(), value));
+        }
+
+        for child in self.children {
+            child.into_routes(route, routes);
+        }
+
+        route.truncate(len);
+    }
+
     // add a child node, keeping wildcards at the end
     fn add_child(&mut self, child: Node<T>) -> usize {
         let len = self.children.len();
@@ -333,7 +373,13 @@ impl<T> Node<T> {
     // It's a bit sad that we have to introduce unsafe here but rust doesn't really have a way
     // to abstract over mutability, so UnsafeCell lets us avoid having to duplicate logic between
     // `at` and `at_mut`.
-    pub fn at(&self, full_path: &str) -> Result<(&T, Params), MatchError> {
+    //
+    // the filter function is called with the value and params of a matched route. when it rejects
+    // the value matching falls through to the next candidate route by backtracking.
+    pub fn at_with<'n, F, R>(&'n self, full_path: &str, mut filter: F) -> Result<(R, Params), MatchError>
+    where
+        F: FnMut(&'n T, &Params) -> Option<R>,
+    {
         let mut current = self;
         let mut path = full_path;
         let mut backtracking = false;
@@ -433,7 +479,11 @@ impl<T> Node<T> {
 
                                         // found the matching value
                                         if let Some(ref value) = current.value {
-                                            return Ok((value, params));
+                                            if let Some(value) = filter(value, &params) {
+                                                return Ok((value, params));
+                                            }
+                                            try_backtrack!();
+                                            return Err(MatchError::NotFound);
                                         }
 
                                         // check the child node in case the path is missing a trailing slash
@@ -461,7 +511,11 @@ impl<T> Node<T> {
                                 // either this node has the value or there is no match
                                 if let Some(ref value) = current.value {
                                     params.push(current.prefix.slice(1..), path);
-                                    return Ok((value, params));
+                                    if let Some(value) = filter(value, &params) {
+                                        return Ok((value, params));
+                                    }
+                                    try_backtrack!();
+                                    return Err(MatchError::NotFound);
                                 }
                             }
                             _ => unreachable!(),
@@ -475,7 +529,11 @@ impl<T> Node<T> {
             // this is it, we should have reached the node containing the value
             if current.prefix.eq(path) {
                 if let Some(ref value) = current.value {
-                    return Ok((value, params));
+                    if let Some(value) = filter(value, &params) {
+                        return Ok((value, params));
+                    }
+                    try_backtrack!();
+                    return Err(MatchError::NotFound);
                 }
 
                 // nope, try backtracking
@@ -0,0 +1,198 @@
+use alloc::{string::String, vec::Vec};
+
+use super::UrlError;
+
+/// A collection of named routes that can generate URL path from route name and parameter values.
+///
+/// ```rust
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut router = xitca_router::Router::new();
+/// router.insert_named("user", "/users/:id", true)?;
+/// router.insert_named("file", "/static/*path", true)?;
+///
+/// let url = router.urls().url_for("user", [("id", "996")])?;
+/// assert_eq!(url, "/users/996");
+///
+/// // catch-all parameter keeps it's path separator.
+/// let url = router.urls().url_for("file", [("path", "css/main style.css")])?;
+/// assert_eq!(url, "/static/css/main%20style.css");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Urls {
+    routes: Vec<(String, String)>,
+}
+
+impl Urls {
+    /// Construct an empty collection.
+    pub const fn new() -> Self {
+        Self { routes: Vec::new() }
+    }
+
+    /// Returns `true` if there is no named route.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.routes.is_empty()
+    }
+
+    /// Returns the route registered under the given name.
+    pub fn route(&self, name: &str) -> Option<&str> {
+        self.routes
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, route)| route.as_str())
+    }
+
+    /// Generate URL path of route registered under the given name.
+    ///
+    /// Parameter values are percent-encoded. Path separator in value of catch-all parameter is
+    /// preserved.
+    pub fn url_for<I, K, V>(&self, name: &str, params: I) -> Result<String, UrlError>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        let route = self.route(name).ok_or(UrlError::NotFound)?;
+        let params = params.into_iter().collect::<Vec<_>>();
+
+        let mut url = String::with_capacity(route.len());
+        let mut rest = route;
+
+        while let Some(i) = rest.find([':', '*', '{']) {
+            url.push_str(&rest[..i]);
+
+            let (key, catch_all) = match rest.as_bytes()[i] {
+                // brace style parameter with optional constraint. e.g. {id:int}
+                b'{' => {
+                    let end = rest[i..].find('}').map(|end| i + end).unwrap_or(rest.len());
+                    let param = &rest[i + 1..end];
+                    rest = rest.get(end + 1..).unwrap_or_default();
+
+                    let name = param.split_once(':').map(|(name, _)| name).unwrap_or(param);
+                    match name.strip_prefix('*') {
+                        Some(name) => (name, true),
+                        None => (name, false),
+                    }
+                }
+                b => {
+                    let catch_all = b == b'*';
+                    rest = &rest[i + 1..];
+
+                    let end = if catch_all {
+                        rest.len()
+                    } else {
+                        rest.find('/').unwrap_or(rest.len())
+                    };
+                    let (key, tail) = rest.split_at(end);
+                    rest = tail;
+                    (key, catch_all)
+                }
+            };
+
+            let value = params
+                .iter()
+                .find(|(k, _)| k.as_ref() == key)
+                .map(|(_, v)| v.as_ref())
+                .ok_or_else(|| UrlError::MissingParam(key.into()))?;
+
+            if catch_all {
+                encode(value.trim_start_matches('/'), true, &mut url);
+            } else {
+                encode(value, false, &mut url);
+            }
+        }
+
+        url.push_str(rest);
+
+        Ok(url)
+    }
+
+    /// Merge named routes of other collection into self. Route of other with a name already
+    /// registered in self is ignored.
+    pub fn merge(&mut self, other: &Self) {
+        for (name, route) in other.routes.iter() {
+            if self.route(name).is_none() {
+                self.routes.push((name.clone(), route.clone()));
+            }
+        }
+    }
+
+    pub(crate) fn insert(&mut self, name: String, route: String) {
+        self.routes.push((name, route));
+    }
+
+    pub(crate) fn remove_route(&mut self, route: &str) {
+        self.routes.retain(|(_, r)| r != route);
+    }
+}
+
+// percent-encode bytes that are not allowed in path segment.
+fn encode(value: &str, keep_slash: bool, url: &mut String) {
+    const HEX: &[u8; 16] = b"0123456789ABCDEF";
+
+    for &b in value.as_bytes() {
+        match b {
+            b'A'..=b'Z'
+            | b'a'..=b'z'
+            | b'0'..=b'9'
+            | b'-'
+            | b'.'
+            | b'_'
+            | b'~'
+            | b'!'
+            | b'$'
+            | b'&'
+            | b'\''
+            | b'('
+            | b')'
+            | b'*'
+            | b'+'
+            | b','
+            | b';'
+            | b'='
+            | b':'
+            | b'@' => url.push(b as char),
+            b'/' if keep_slash => url.push('/'),
+            _ => {
+                url.push('%');
+                url.push(HEX[(b >> 4) as usize] as char);
+                url.push(HEX[(b & 0xf) as usize] as char);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn url_for() {
+        let mut urls = Urls::new();
+        urls.insert("post".into(), "/users/:id/posts/:post".into());
+        urls.insert("file".into(), "/static/*path".into());
+
+        let url = urls.url_for("post", [("post", "a/b?c"), ("id", "1")]).unwrap();
+        assert_eq!(url, "/users/1/posts/a%2Fb%3Fc");
+
+        let url = urls.url_for("file", [("path", "/dir/100%.txt")]).unwrap();
+        assert_eq!(url, "/static/dir/100%25.txt");
+
+        urls.insert("archive".into(), "/v{major:int}/{file}.{ext:[a-z]+}/{*rest}".into());
+        let url = urls
+            .url_for(
+                "archive",
+                [("major", "2"), ("file", "a b"), ("ext", "gz"), ("rest", "c/d")],
+            )
+            .unwrap();
+        assert_eq!(url, "/v2/a%20b.gz/c/d");
+
+        assert_eq!(
+            urls.url_for("post", [("id", "1")]).unwrap_err(),
+            UrlError::MissingParam("post".into())
+        );
+        assert_eq!(urls.url_for("user", [("id", "1")]).unwrap_err(), UrlError::NotFound);
+    }
+}
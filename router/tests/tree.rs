@@ -16,6 +16,107 @@ fn issue_22() {
     assert_eq!(x.at("/foo/").unwrap_err(), MatchError::ExtraTrailingSlash);
 }
 
+#[test]
+fn remove_and_replace() {
+    let routes = [
+        "/",
+        "/cmd/:tool/:sub",
+        "/cmd/:tool/",
+        "/src/*filepath",
+        "/search/",
+        "/search/:query",
+        "/user_:name",
+        "/user_:name/about",
+        "/files/:dir/*filepath",
+        "/info/:user/public",
+        "/info/:user/project/:project",
+    ];
+
+    let mut router = Router::new();
+    for route in routes {
+        router.insert(route, route).unwrap();
+    }
+
+    assert_eq!(router.remove("/cmd/:tool"), None);
+    assert_eq!(router.remove("/user_:nam"), None);
+    assert_eq!(router.remove("/search/:query"), Some("/search/:query"));
+    assert_eq!(router.remove("/search/:query"), None);
+    assert_eq!(router.at("/search/rust").unwrap_err(), MatchError::NotFound);
+
+    for route in routes.iter().filter(|r| **r != "/search/:query") {
+        let path = route.replace([':', '*'], "");
+        assert_eq!(*router.at(&path).unwrap().value, *route);
+    }
+
+    assert_eq!(
+        router.replace("/files/:dir/*filepath", "files").unwrap(),
+        Some("/files/:dir/*filepath")
+    );
+    assert_eq!(*router.at("/files/a/b/c").unwrap().value, "files");
+    assert_eq!(router.replace("/search/:query", "search").unwrap(), None);
+    assert_eq!(*router.at("/search/rust").unwrap().value, "search");
+}
+
+#[test]
+fn param_constraints() {
+    let mut router = Router::new();
+    router.insert("/users/me", "me").unwrap();
+    router.insert("/users/{id:int}", "id").unwrap();
+    router.insert("/users/{slug:[a-z-]+}", "slug").unwrap();
+    router.insert("/users/{name}/posts", "posts").unwrap();
+    router.insert("/files/{file}.{ext:[a-z]+}", "file").unwrap();
+    router.insert("/files/{name}", "name").unwrap();
+    router.insert("/assets/{*path}", "assets").unwrap();
+
+    assert_eq!(
+        router.insert("/users/{uid:int}", "uid").unwrap_err(),
+        InsertError::Conflict {
+            with: "/users/{id:int}".into()
+        }
+    );
+    assert_eq!(
+        router.insert("/users/{id:hex}", "hex").unwrap_err(),
+        InsertError::InvalidParam("{id:hex}".into())
+    );
+
+    let matched = router.at("/users/-42").unwrap();
+    assert_eq!(*matched.value, "id");
+    assert_eq!(matched.params.get("id"), Some("-42"));
+
+    let matched = router.at("/users/hello-world").unwrap();
+    assert_eq!(*matched.value, "slug");
+    assert_eq!(matched.params.get("slug"), Some("hello-world"));
+
+    assert_eq!(*router.at("/users/me").unwrap().value, "me");
+    assert_eq!(router.at("/users/Hello").unwrap_err(), MatchError::NotFound);
+
+    let matched = router.at("/users/Hello/posts").unwrap();
+    assert_eq!(*matched.value, "posts");
+    assert_eq!(matched.params.get("name"), Some("Hello"));
+
+    let matched = router.at("/files/archive.tar.gz").unwrap();
+    assert_eq!(*matched.value, "file");
+    assert_eq!(matched.params.get("file"), Some("archive.tar"));
+    assert_eq!(matched.params.get("ext"), Some("gz"));
+
+    // constraint failure falls through to the next candidate.
+    let matched = router.at("/files/archive.7z").unwrap();
+    assert_eq!(*matched.value, "name");
+    assert_eq!(matched.params.get("name"), Some("archive.7z"));
+    assert_eq!(*router.at("/files/.gz").unwrap().value, "name");
+
+    let matched = router.at("/assets/css/main.css").unwrap();
+    assert_eq!(matched.params.get("path"), Some("css/main.css"));
+
+    assert_eq!(router.remove("/users/{id:int}"), Some("id"));
+    assert_eq!(router.at("/users/42").unwrap_err(), MatchError::NotFound);
+    assert_eq!(router.replace("/users/{slug:[a-z-]+}", "slug2").unwrap(), Some("slug"));
+    assert_eq!(*router.at("/users/foo").unwrap().value, "slug2");
+    assert_eq!(router.remove("/users/{slug:[a-z-]+}"), Some("slug2"));
+    assert_eq!(router.at("/users/foo").unwrap_err(), MatchError::NotFound);
+    assert_eq!(*router.at("/users/foo/posts").unwrap().value, "posts");
+}
+
 match_tests! {
     basic {
         routes = [
@@ -22,6 +22,9 @@ pub struct Builder {
     pub(crate) enable_signal: bool,
     pub(crate) shutdown_timeout: Duration,
     pub(crate) on_worker_start: Box<dyn Fn() -> Pin<Box<dyn Future<Output = ()> + Send>> + Send + Sync>,
+    // names of listeners that can not be handed over to new process on upgrade.
+    #[cfg(unix)]
+    pub(crate) fixed_listeners: Vec<String>,
     backlog: u32,
 }
 
@@ -43,6 +46,8 @@ impl Builder {
             enable_signal: true,
             shutdown_timeout: Duration::from_secs(30),
             on_worker_start: Box::new(|| Box::pin(async {})),
+            #[cfg(unix)]
+            fixed_listeners: Vec::new(),
             backlog: 2048,
         }
     }
@@ -134,34 +139,50 @@ impl Builder {
         self
     }
 
-    pub fn listen<N, F, St>(self, name: N, listener: net::TcpListener, factory: F) -> Self
+    pub fn listen<N, F, St>(mut self, name: N, listener: net::TcpListener, factory: F) -> Self
     where
         N: AsRef<str>,
         F: BuildServiceFn<St>,
         St: From<Stream> + Send + 'static,
     {
+        #[cfg(unix)]
+        self.fixed_listeners.push(name.as_ref().to_owned());
         self._listen(name, Some(listener), factory)
     }
 
     pub fn build(self) -> ServerFuture {
         let enable_signal = self.enable_signal;
         match Server::new(self) {
-            Ok(server) => ServerFuture::Init { server, enable_signal },
+            Ok(server) => {
+                // notify server process being upgraded that this process is ready.
+                #[cfg(unix)]
+                crate::net::notify_upgrade_ready();
+                ServerFuture::Init { server, enable_signal }
+            }
             Err(e) => ServerFuture::Error(e),
         }
     }
 
-    fn _listen<N, L, F, St>(mut self, name: N, listener: L, factory: F) -> Self
+    fn _listen<N, L, F, St>(self, name: N, listener: L, factory: F) -> Self
     where
         N: AsRef<str>,
         L: AsListener + 'static,
         F: BuildServiceFn<St>,
         St: From<Stream> + Send + 'static,
+    {
+        self._listen_boxed(name, vec![Box::new(listener)], factory)
+    }
+
+    fn _listen_boxed<N, F, St>(mut self, name: N, listeners: Vec<Box<dyn AsListener>>, factory: F) -> Self
+    where
+        N: AsRef<str>,
+        F: BuildServiceFn<St>,
+        St: From<Stream> + Send + 'static,
     {
         self.listeners
             .entry(name.as_ref().to_string())
             .or_insert_with(Vec::new)
-            .push(Box::new(listener));
+            .extend(listeners);
 
         let factory = Factory::new_boxed(factory);
 
@@ -194,6 +215,14 @@ impl Builder {
         F: BuildServiceFn<St>,
         St: From<Stream> + Send + 'static,
     {
+        #[cfg(unix)]
+        {
+            let listeners = Self::take_upgrade_listeners(name.as_ref(), Some(crate::net::ListenAddr::Tcp(addr)))?;
+            if !listeners.is_empty() {
+                return Ok(self._listen_boxed(name, listeners, factory));
+            }
+        }
+
         let socket = if addr.is_ipv4() {
             Socket::new(Domain::IPV4, Type::STREAM, Some(Protocol::TCP))?
         } else {
@@ -204,9 +233,9 @@ impl Builder {
         socket.bind(&SockAddr::from(addr))?;
         socket.listen(self.backlog as _)?;
 
-        let listener = socket.into();
+        let listener: net::TcpListener = socket.into();
 
-        Ok(self.listen(name, listener, factory))
+        Ok(self._listen(name, Some(listener), factory))
     }
 }
 
@@ -219,6 +248,11 @@ impl Builder {
         F: BuildServiceFn<St>,
         St: From<Stream> + Send + 'static,
     {
+        let listeners = Self::take_upgrade_listeners(name.as_ref(), Some(crate::net::ListenAddr::Unix(path.as_ref())))?;
+        if !listeners.is_empty() {
+            return Ok(self._listen_boxed(name, listeners, factory));
+        }
+
         // The path must not exist when we try to bind.
         // Try to remove it to avoid bind error.
         if let Err(e) = std::fs::remove_file(path.as_ref()) {
@@ -230,17 +264,95 @@ impl Builder {
 
         let listener = std::os::unix::net::UnixListener::bind(path)?;
 
-        Ok(self.listen_unix(name, listener, factory))
+        Ok(self._listen(name, Some(listener), factory))
     }
 
-    pub fn listen_unix<N, F, St>(self, name: N, listener: std::os::unix::net::UnixListener, factory: F) -> Self
+    pub fn listen_unix<N, F, St>(mut self, name: N, listener: std::os::unix::net::UnixListener, factory: F) -> Self
     where
         N: AsRef<str>,
         F: BuildServiceFn<St>,
         St: From<Stream> + Send + 'static,
     {
+        self.fixed_listeners.push(name.as_ref().to_owned());
         self._listen(name, Some(listener), factory)
     }
+
+    /// Listen on an inherited socket file descriptor. Tcp and Unix stream sockets are supported.
+    ///
+    /// Ownership of fd is transferred to [Builder] and the fd is closed when server is dropped.
+    ///
+    /// # Examples:
+    /// ```
+    /// # use std::os::unix::io::OwnedFd;
+    /// # use xitca_io::net::TcpStream;
+    /// # use xitca_server::Builder;
+    /// # use xitca_service::fn_service;
+    /// let fd = OwnedFd::from(std::net::TcpListener::bind("127.0.0.1:0").unwrap());
+    ///
+    /// let builder = Builder::new()
+    ///     .listen_fd("http", fd, || fn_service(|_: TcpStream| async { Ok::<_, ()>(()) }))
+    ///     .unwrap();
+    /// ```
+    pub fn listen_fd<N, F, St>(mut self, name: N, fd: std::os::unix::io::OwnedFd, factory: F) -> io::Result<Self>
+    where
+        N: AsRef<str>,
+        F: BuildServiceFn<St>,
+        St: From<Stream> + Send + 'static,
+    {
+        let listener = crate::net::from_fd(fd)?;
+        self.fixed_listeners.push(name.as_ref().to_owned());
+        Ok(self._listen_boxed(name, vec![listener], factory))
+    }
+
+    // listener sockets handed over by server process being upgraded. See ServerHandle::upgrade.
+    fn take_upgrade_listeners(
+        name: &str,
+        addr: Option<crate::net::ListenAddr<'_>>,
+    ) -> io::Result<Vec<Box<dyn AsListener>>> {
+        crate::net::take_upgrade_fds(name, addr)
+            .into_iter()
+            .map(crate::net::from_fd)
+            .collect()
+    }
+
+    /// Listen on sockets passed by systemd socket activation.
+    ///
+    /// Sockets are looked up from `LISTEN_FDS` and `LISTEN_FDNAMES` environment variables and the ones
+    /// with file descriptor name equal to given name are used. (`FileDescriptorName=` in systemd
+    /// socket unit. Sockets without a name are named `unknown`.) A socket can only be taken once per
+    /// process.
+    ///
+    /// Sockets with the same name handed over by server process being upgraded are used instead
+    /// when current process is started by [ServerHandle::upgrade](crate::ServerHandle::upgrade).
+    ///
+    /// Returns error when no socket matches the name.
+    pub fn listen_systemd<N, F, St>(self, name: N, factory: F) -> io::Result<Self>
+    where
+        N: AsRef<str>,
+        F: BuildServiceFn<St>,
+        St: From<Stream> + Send + 'static,
+    {
+        let listeners = Self::take_upgrade_listeners(name.as_ref(), None)?;
+        if !listeners.is_empty() {
+            return Ok(self._listen_boxed(name, listeners, factory));
+        }
+
+        let fds = crate::net::take_systemd_fds(name.as_ref());
+
+        if fds.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("no socket named {} passed from systemd", name.as_ref()),
+            ));
+        }
+
+        let listeners = fds
+            .into_iter()
+            .map(crate::net::from_fd)
+            .collect::<io::Result<Vec<_>>>()?;
+
+        Ok(self._listen_boxed(name, listeners, factory))
+    }
 }
 
 #[cfg(feature = "http3")]
@@ -268,6 +380,9 @@ impl Builder {
 
         let builder = xitca_io::net::UdpListenerBuilder::new(addr, config).backlog(self.backlog);
 
+        #[cfg(unix)]
+        self.fixed_listeners.push(name.as_ref().to_owned());
+
         self.listeners
             .get_mut(name.as_ref())
             .unwrap()
@@ -277,7 +392,7 @@ impl Builder {
     }
 
     pub fn bind_h3<N, A, F, St>(
-        self,
+        mut self,
         name: N,
         addr: A,
         config: xitca_io::net::H3ServerConfig,
@@ -296,6 +411,9 @@ impl Builder {
 
         let builder = xitca_io::net::UdpListenerBuilder::new(addr, config).backlog(self.backlog);
 
+        #[cfg(unix)]
+        self.fixed_listeners.push(name.as_ref().to_owned());
+
         Ok(self._listen(name, Some(builder), factory))
     }
 }
@@ -1,6 +1,9 @@
 //! Multi-threaded server for Tcp/Udp/UnixDomain handling.
 
-#![forbid(unsafe_code)]
+// unsafe code is only allowed in net::fd module for passing socket file descriptors between processes.
+// forbid can not be overridden by module level allow so deny is used when the module exists.
+#![cfg_attr(unix, deny(unsafe_code))]
+#![cfg_attr(not(unix), forbid(unsafe_code))]
 
 mod builder;
 mod server;
@@ -10,7 +13,7 @@ mod worker;
 pub mod net;
 
 pub use builder::Builder;
-pub use server::{ServerFuture, ServerHandle};
+pub use server::{ServerFuture, ServerHandle, WorkerStats};
 
 #[cfg(all(not(target_os = "linux"), feature = "io-uring"))]
 compile_error!("io_uring can only be used on linux system");
@@ -29,4 +32,89 @@ mod test {
             })
             .build();
     }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_listen_fd() {
+        use std::os::unix::io::OwnedFd;
+
+        let fd = OwnedFd::from(std::net::TcpListener::bind("localhost:0").unwrap());
+        let _server = crate::builder::Builder::new()
+            .listen_fd("test", fd, || fn_service(|_: TcpStream| async { Ok::<_, ()>(()) }))
+            .unwrap()
+            .build();
+
+        let fd = OwnedFd::from(std::net::UdpSocket::bind("localhost:0").unwrap());
+        let res = crate::builder::Builder::new()
+            .listen_fd("test", fd, || fn_service(|_: TcpStream| async { Ok::<_, ()>(()) }));
+        assert!(res.is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_upgrade_fixed_listener() {
+        let listener = std::net::TcpListener::bind("localhost:0").unwrap();
+        let server = crate::builder::Builder::new()
+            .worker_threads(1)
+            .disable_signal()
+            .listen("test", listener, || {
+                fn_service(|_: TcpStream| async { Ok::<_, ()>(()) })
+            })
+            .build();
+
+        match server {
+            crate::ServerFuture::Init { ref server, .. } => {
+                let err = server.upgrade().unwrap_err();
+                assert_eq!(err.kind(), std::io::ErrorKind::Unsupported);
+            }
+            _ => panic!("server failed to build"),
+        }
+    }
+
+    #[test]
+    fn test_pause_stats() {
+        use std::{io::Read, net, thread};
+
+        let listener = net::TcpListener::bind("localhost:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let mut server = crate::builder::Builder::new()
+            .worker_threads(1)
+            .disable_signal()
+            .listen("test", listener, || {
+                fn_service(|_: TcpStream| async { Ok::<_, ()>(()) })
+            })
+            .build();
+
+        let handle = server.handle().unwrap();
+        let join = thread::spawn(move || server.wait());
+
+        // connection is closed by service right after accepted.
+        let connect = || net::TcpStream::connect(addr).unwrap();
+
+        assert_eq!(connect().read(&mut [0]).unwrap(), 0);
+
+        handle.pause();
+
+        // connection is queued in backlog and not accepted.
+        let mut stream = connect();
+        assert_eq!(handle.stats()[0].accepted, 1);
+
+        handle.resume();
+        assert_eq!(stream.read(&mut [0]).unwrap(), 0);
+
+        // connection is counted as active until it's service is dropped.
+        while handle.stats()[0].active_connections != 0 {
+            thread::yield_now();
+        }
+
+        let stats = handle.stats();
+        assert_eq!(stats.len(), 1);
+        assert_eq!(stats[0].active_connections, 0);
+        assert_eq!(stats[0].accepted, 2);
+        assert_eq!(stats[0].accept_errors, 0);
+
+        handle.stop(false);
+        join.join().unwrap().unwrap();
+    }
 }
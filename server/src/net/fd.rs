@@ -0,0 +1,512 @@
+//! listeners from inherited socket file descriptors.
+
+use std::{
+    env,
+    io::{self, Read, Write},
+    mem, net,
+    os::unix::{
+        io::{AsRawFd, FromRawFd, IntoRawFd, OwnedFd, RawFd},
+        net::{UnixListener, UnixStream},
+        process::CommandExt,
+    },
+    path::Path,
+    process::Command,
+    ptr,
+    sync::Mutex,
+    time::Duration,
+};
+
+use socket2::{Domain, SockRef, Socket, Type};
+use tracing::{error, info};
+
+use super::AsListener;
+
+// first file descriptor passed by systemd socket activation.
+const LISTEN_FDS_START: RawFd = 3;
+
+// socket file descriptors passed by systemd that are not taken by any listener yet.
+// environment variables are parsed once on first use and fds can only be taken once per process.
+// variables are left untouched as child processes ignore them by LISTEN_PID.
+static SYSTEMD_FDS: Mutex<Option<Vec<(String, RawFd)>>> = Mutex::new(None);
+
+// environment variable holding fd of unix socket connected to the server process being upgraded.
+const UPGRADE_FD: &str = "XITCA_SERVER_UPGRADE_FD";
+
+// environment variable holding pid of the server process being upgraded. fd is ignored when it's
+// not the parent of current process so child processes of new process can not mistake it as theirs.
+const UPGRADE_PID: &str = "XITCA_SERVER_UPGRADE_PID";
+
+// max number of fds sent in one message. (SCM_MAX_FD of linux)
+const MAX_FDS: usize = 253;
+
+// listener sockets handed over by the server process being upgraded.
+static UPGRADE: Mutex<Option<Upgrade>> = Mutex::new(None);
+
+struct Upgrade {
+    stream: Option<UnixStream>,
+    fds: Vec<(String, OwnedFd)>,
+}
+
+/// Construct listener from file descriptor of a listening socket. Tcp and Unix stream sockets are
+/// supported.
+pub(crate) fn from_fd(fd: OwnedFd) -> io::Result<Box<dyn AsListener>> {
+    let fd = fd.into_raw_fd();
+
+    // SAFETY: fd is owned and it's ownership is transferred to socket.
+    let socket = unsafe { Socket::from_raw_fd(fd) };
+
+    if socket.r#type()? != Type::STREAM {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("file descriptor {fd} is not a stream socket"),
+        ));
+    }
+
+    socket.set_cloexec(true)?;
+
+    let addr = socket.local_addr()?;
+
+    if addr.as_socket().is_some() {
+        let listener: net::TcpListener = socket.into();
+        Ok(Box::new(Some(listener)))
+    } else if i32::from(addr.family()) == i32::from(Domain::UNIX) {
+        let listener: UnixListener = socket.into();
+        Ok(Box::new(Some(listener)))
+    } else {
+        Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("file descriptor {fd} is not a Tcp or Unix socket"),
+        ))
+    }
+}
+
+/// Take socket file descriptors passed by systemd with given name.
+pub(crate) fn take_systemd_fds(name: &str) -> Vec<OwnedFd> {
+    let mut fds = SYSTEMD_FDS.lock().unwrap();
+
+    let fds = fds.get_or_insert_with(|| {
+        parse_env(
+            env::var("LISTEN_PID").ok().as_deref(),
+            env::var("LISTEN_FDS").ok().as_deref(),
+            env::var("LISTEN_FDNAMES").ok().as_deref(),
+            std::process::id(),
+        )
+    });
+
+    let mut taken = Vec::new();
+
+    fds.retain(|(n, fd)| {
+        let matched = n == name;
+        if matched {
+            // SAFETY: fds passed by systemd are owned by this process and removed after taken.
+            taken.push(unsafe { OwnedFd::from_raw_fd(*fd) });
+        }
+        !matched
+    });
+
+    taken
+}
+
+/// Local address of listener socket handed over by the server process being upgraded.
+pub(crate) enum ListenAddr<'a> {
+    Tcp(net::SocketAddr),
+    Unix(&'a Path),
+}
+
+impl ListenAddr<'_> {
+    fn matches(&self, fd: &OwnedFd) -> bool {
+        match *self {
+            Self::Tcp(addr) => SockRef::from(fd)
+                .local_addr()
+                .map_or(false, |a| a.as_socket() == Some(addr)),
+            Self::Unix(path) => fd
+                .try_clone()
+                .and_then(|fd| UnixListener::from(fd).local_addr())
+                .map_or(false, |a| a.as_pathname() == Some(path)),
+        }
+    }
+}
+
+/// Take listener sockets handed over by the server process being upgraded with given name. When addr
+/// is given only the socket bound to it is taken.
+pub(crate) fn take_upgrade_fds(name: &str, addr: Option<ListenAddr<'_>>) -> Vec<OwnedFd> {
+    let mut upgrade = UPGRADE.lock().unwrap();
+
+    let upgrade = upgrade.get_or_insert_with(|| {
+        let Some(fd) = parse_upgrade_env(
+            env::var(UPGRADE_FD).ok().as_deref(),
+            env::var(UPGRADE_PID).ok().as_deref(),
+            std::os::unix::process::parent_id(),
+        ) else {
+            return Upgrade {
+                stream: None,
+                fds: Vec::new(),
+            };
+        };
+
+        // SAFETY: fd is inherited from the server process being upgraded and owned by this process.
+        let stream = unsafe { UnixStream::from_raw_fd(fd) };
+
+        let fds = recv_listeners(&stream).unwrap_or_else(|e| {
+            error!("Can not receive listeners from upgraded server: {e}");
+            Vec::new()
+        });
+
+        Upgrade {
+            stream: Some(stream),
+            fds,
+        }
+    });
+
+    let (taken, rest) = mem::take(&mut upgrade.fds)
+        .into_iter()
+        .partition::<Vec<_>, _>(|(n, fd)| n == name && addr.as_ref().map_or(true, |addr| addr.matches(fd)));
+
+    upgrade.fds = rest;
+
+    taken.into_iter().map(|(_, fd)| fd).collect()
+}
+
+/// Notify the server process being upgraded that this process is ready to serve. Listeners handed
+/// over but not taken are closed.
+pub(crate) fn notify_upgrade_ready() {
+    let mut upgrade = UPGRADE.lock().unwrap();
+    if let Some(upgrade) = upgrade.as_mut() {
+        upgrade.fds.clear();
+        if let Some(mut stream) = upgrade.stream.take() {
+            if let Err(e) = stream.write_all(b"1") {
+                error!("Can not notify upgraded server: {e}");
+            }
+        }
+    }
+}
+
+/// Re-execute current binary with the same arguments and hand listeners to the new process. Returns
+/// when the new process is ready to serve.
+pub(crate) fn upgrade(listeners: &[(String, OwnedFd)], timeout: Duration) -> io::Result<()> {
+    let mut cmd = Command::new(env::current_exe()?);
+    cmd.args(env::args_os().skip(1));
+    spawn_with_listeners(cmd, listeners, timeout)
+}
+
+/// Spawn child process with given command and hand listeners to it. Returns when the child process
+/// is ready to serve.
+fn spawn_with_listeners(mut cmd: Command, listeners: &[(String, OwnedFd)], timeout: Duration) -> io::Result<()> {
+    if listeners.is_empty() || listeners.len() > MAX_FDS {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("can not hand over {} listeners", listeners.len()),
+        ));
+    }
+
+    let (stream, child_stream) = UnixStream::pair()?;
+    let child_fd = child_stream.as_raw_fd();
+
+    cmd.env(UPGRADE_FD, child_fd.to_string())
+        .env(UPGRADE_PID, std::process::id().to_string())
+        // sockets passed by systemd are handed over with other listeners.
+        .env_remove("LISTEN_PID")
+        .env_remove("LISTEN_FDS")
+        .env_remove("LISTEN_FDNAMES");
+
+    // SAFETY: fcntl is async signal safe. clear close on exec flag so the new process inherits it.
+    unsafe {
+        cmd.pre_exec(move || match libc::fcntl(child_fd, libc::F_SETFD, 0) {
+            -1 => Err(io::Error::last_os_error()),
+            _ => Ok(()),
+        });
+    }
+
+    let mut child = cmd.spawn()?;
+    drop(child_stream);
+
+    info!("Spawned new server process: {}", child.id());
+
+    let res = send_listeners(&stream, listeners).and_then(|_| {
+        stream.set_read_timeout(Some(timeout))?;
+        let mut ack = [0];
+        match (&stream).read(&mut ack)? {
+            1 => Ok(()),
+            _ => Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "new server process exited before ready",
+            )),
+        }
+    });
+
+    if res.is_err() {
+        let _ = child.kill();
+        let _ = child.wait();
+    }
+
+    res
+}
+
+fn send_listeners(stream: &UnixStream, listeners: &[(String, OwnedFd)]) -> io::Result<()> {
+    let names = listeners
+        .iter()
+        .map(|(name, _)| name.as_str())
+        .collect::<Vec<_>>()
+        .join("\n");
+    let fds = listeners.iter().map(|(_, fd)| fd.as_raw_fd()).collect::<Vec<_>>();
+    send_fds(stream, names.as_bytes(), &fds)
+}
+
+fn recv_listeners(stream: &UnixStream) -> io::Result<Vec<(String, OwnedFd)>> {
+    let (names, fds) = recv_fds(stream)?;
+    let names = String::from_utf8(names).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+    let names = names.split('\n');
+    if names.clone().count() != fds.len() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "listener names do not match received sockets",
+        ));
+    }
+
+    Ok(names.map(String::from).zip(fds).collect())
+}
+
+// send payload and file descriptors with SCM_RIGHTS control message.
+fn send_fds(stream: &UnixStream, payload: &[u8], fds: &[RawFd]) -> io::Result<()> {
+    let fds_len = mem::size_of_val(fds);
+
+    // SAFETY: pure computation of control message buffer size.
+    let space = unsafe { libc::CMSG_SPACE(fds_len as _) } as usize;
+    // u64 buffer for alignment of cmsghdr.
+    let mut cmsg_buf = vec![0u64; (space + 7) / 8];
+
+    let mut iov = libc::iovec {
+        iov_base: payload.as_ptr() as *mut _,
+        iov_len: payload.len(),
+    };
+
+    // SAFETY: msghdr is plain C struct and all zero is a valid value.
+    let mut msg: libc::msghdr = unsafe { mem::zeroed() };
+    msg.msg_iov = &mut iov;
+    msg.msg_iovlen = 1;
+    msg.msg_control = cmsg_buf.as_mut_ptr().cast();
+    msg.msg_controllen = space as _;
+
+    // SAFETY: control buffer is large enough for one control message with all fds.
+    let n = unsafe {
+        let cmsg = libc::CMSG_FIRSTHDR(&msg);
+        (*cmsg).cmsg_level = libc::SOL_SOCKET;
+        (*cmsg).cmsg_type = libc::SCM_RIGHTS;
+        (*cmsg).cmsg_len = libc::CMSG_LEN(fds_len as _) as _;
+        ptr::copy_nonoverlapping(fds.as_ptr().cast::<u8>(), libc::CMSG_DATA(cmsg), fds_len);
+
+        libc::sendmsg(stream.as_raw_fd(), &msg, 0)
+    };
+
+    match n {
+        -1 => Err(io::Error::last_os_error()),
+        n if n as usize != payload.len() => Err(io::ErrorKind::WriteZero.into()),
+        _ => Ok(()),
+    }
+}
+
+// receive payload and file descriptors of SCM_RIGHTS control message.
+fn recv_fds(stream: &UnixStream) -> io::Result<(Vec<u8>, Vec<OwnedFd>)> {
+    let mut buf = vec![0u8; 64 * 1024];
+
+    // SAFETY: pure computation of control message buffer size.
+    let space = unsafe { libc::CMSG_SPACE((MAX_FDS * mem::size_of::<RawFd>()) as _) } as usize;
+    let mut cmsg_buf = vec![0u64; (space + 7) / 8];
+
+    let mut iov = libc::iovec {
+        iov_base: buf.as_mut_ptr().cast(),
+        iov_len: buf.len(),
+    };
+
+    // SAFETY: msghdr is plain C struct and all zero is a valid value.
+    let mut msg: libc::msghdr = unsafe { mem::zeroed() };
+    msg.msg_iov = &mut iov;
+    msg.msg_iovlen = 1;
+    msg.msg_control = cmsg_buf.as_mut_ptr().cast();
+    msg.msg_controllen = space as _;
+
+    // received fds are close-on-exec so they are not leaked to child processes spawned before they
+    // are taken by listeners. it's set atomically where supported.
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    let flags = libc::MSG_CMSG_CLOEXEC;
+    #[cfg(not(any(target_os = "linux", target_os = "android")))]
+    let flags = 0;
+
+    // SAFETY: buffers outlive the call.
+    let n = unsafe { libc::recvmsg(stream.as_raw_fd(), &mut msg, flags) };
+    if n == -1 {
+        return Err(io::Error::last_os_error());
+    }
+
+    let mut fds = Vec::new();
+
+    // SAFETY: control messages are initialized by recvmsg and iterated within msg_controllen.
+    unsafe {
+        let mut cmsg = libc::CMSG_FIRSTHDR(&msg);
+        while !cmsg.is_null() {
+            if (*cmsg).cmsg_level == libc::SOL_SOCKET && (*cmsg).cmsg_type == libc::SCM_RIGHTS {
+                let data = libc::CMSG_DATA(cmsg);
+                let len = ((*cmsg).cmsg_len as usize - (data as usize - cmsg as usize)) / mem::size_of::<RawFd>();
+                for i in 0..len {
+                    let fd = ptr::read_unaligned(data.cast::<RawFd>().add(i));
+                    fds.push(OwnedFd::from_raw_fd(fd));
+                }
+            }
+            cmsg = libc::CMSG_NXTHDR(&msg, cmsg);
+        }
+    }
+
+    #[cfg(not(any(target_os = "linux", target_os = "android")))]
+    for fd in fds.iter() {
+        SockRef::from(fd).set_cloexec(true)?;
+    }
+
+    if msg.msg_flags & libc::MSG_CTRUNC != 0 {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "sockets are truncated"));
+    }
+
+    buf.truncate(n as usize);
+
+    Ok((buf, fds))
+}
+
+// parse environment variables of upgrade and return fd of unix socket connected to parent process.
+fn parse_upgrade_env(fd: Option<&str>, pid: Option<&str>, parent_pid: u32) -> Option<RawFd> {
+    if pid?.trim().parse::<u32>().ok()? != parent_pid {
+        return None;
+    }
+    fd?.trim().parse().ok()
+}
+
+// parse environment variables of systemd socket activation protocol. see sd_listen_fds(3).
+fn parse_env(pid: Option<&str>, fds: Option<&str>, names: Option<&str>, self_pid: u32) -> Vec<(String, RawFd)> {
+    let (Some(pid), Some(fds)) = (pid, fds) else {
+        return Vec::new();
+    };
+
+    if pid.trim().parse::<u32>().ok() != Some(self_pid) {
+        return Vec::new();
+    }
+
+    let Ok(len) = fds.trim().parse::<RawFd>() else {
+        return Vec::new();
+    };
+
+    let mut names = names
+        .map(|names| names.split(':').collect::<Vec<_>>())
+        .unwrap_or_default();
+
+    // names are ignored when they don't match passed fds.
+    if names.len() != len as usize {
+        names.clear();
+    }
+
+    (0..len)
+        .map(|i| {
+            let name = names.get(i as usize).copied().unwrap_or("unknown");
+            (name.to_owned(), LISTEN_FDS_START + i)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let pid = std::process::id();
+        let pid_str = pid.to_string();
+
+        assert_eq!(
+            parse_env(Some(&pid_str), Some("2"), Some("http:admin"), pid),
+            vec![("http".to_owned(), 3), ("admin".to_owned(), 4)]
+        );
+        assert_eq!(
+            parse_env(Some(&pid_str), Some("2"), Some("http"), pid),
+            vec![("unknown".to_owned(), 3), ("unknown".to_owned(), 4)]
+        );
+        assert_eq!(
+            parse_env(Some(&pid_str), Some("1"), None, pid),
+            vec![("unknown".to_owned(), 3)]
+        );
+        assert!(parse_env(Some("1"), Some("1"), None, pid).is_empty());
+        assert!(parse_env(None, Some("1"), None, pid).is_empty());
+
+        assert_eq!(parse_upgrade_env(Some("5"), Some(&pid_str), pid), Some(5));
+        assert_eq!(parse_upgrade_env(Some("5"), Some("1"), pid), None);
+        assert_eq!(parse_upgrade_env(Some("5"), None, pid), None);
+        assert_eq!(parse_upgrade_env(None, Some(&pid_str), pid), None);
+    }
+
+    #[test]
+    fn send_recv_listeners() {
+        let tcp = net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = tcp.local_addr().unwrap();
+
+        let (tx, rx) = UnixStream::pair().unwrap();
+
+        send_listeners(&tx, &[("http".to_owned(), OwnedFd::from(tcp))]).unwrap();
+
+        let mut listeners = recv_listeners(&rx).unwrap();
+        assert_eq!(listeners.len(), 1);
+
+        let (name, fd) = listeners.pop().unwrap();
+        assert_eq!(name, "http");
+
+        // SAFETY: fd is valid and owned.
+        let flags = unsafe { libc::fcntl(fd.as_raw_fd(), libc::F_GETFD) };
+        assert_ne!(flags & libc::FD_CLOEXEC, 0);
+
+        assert_eq!(net::TcpListener::from(fd).local_addr().unwrap(), addr);
+    }
+
+    #[test]
+    fn listen_addr() {
+        let tcp = net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = tcp.local_addr().unwrap();
+        let fd = OwnedFd::from(tcp);
+
+        assert!(ListenAddr::Tcp(addr).matches(&fd));
+        assert!(!ListenAddr::Tcp(net::SocketAddr::from(([127, 0, 0, 1], 0))).matches(&fd));
+        assert!(!ListenAddr::Unix(Path::new("/tmp/xitca-server.sock")).matches(&fd));
+
+        let path = env::temp_dir().join(format!("xitca-server-{}.sock", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let fd = OwnedFd::from(UnixListener::bind(&path).unwrap());
+
+        assert!(ListenAddr::Unix(&path).matches(&fd));
+        assert!(!ListenAddr::Unix(Path::new("/tmp/xitca-server.sock")).matches(&fd));
+        assert!(!ListenAddr::Tcp(addr).matches(&fd));
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    // child process of spawn_listeners test. it does nothing when not spawned by the test.
+    #[test]
+    fn spawn_listeners_child() {
+        let Ok(addr) = env::var("XITCA_SERVER_TEST_ADDR") else {
+            return;
+        };
+
+        let fds = take_upgrade_fds("http", Some(ListenAddr::Tcp(addr.parse().unwrap())));
+        assert_eq!(fds.len(), 1);
+
+        notify_upgrade_ready();
+    }
+
+    #[test]
+    fn spawn_listeners() {
+        let tcp = net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = tcp.local_addr().unwrap();
+
+        let mut cmd = Command::new(env::current_exe().unwrap());
+        cmd.args(["--exact", "net::fd::test::spawn_listeners_child"])
+            .env("XITCA_SERVER_TEST_ADDR", addr.to_string())
+            .stdout(std::process::Stdio::null());
+
+        let listeners = [("http".to_owned(), OwnedFd::from(tcp))];
+        spawn_with_listeners(cmd, &listeners, Duration::from_secs(30)).unwrap();
+    }
+}
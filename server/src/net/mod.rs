@@ -8,6 +8,14 @@ use xitca_io::net::{Listener, TcpListener};
 
 use tracing::info;
 
+// passing socket file descriptors between processes is the only place unsafe code is allowed.
+#[cfg(unix)]
+#[allow(unsafe_code)]
+mod fd;
+
+#[cfg(unix)]
+pub(crate) use self::fd::{from_fd, notify_upgrade_ready, take_systemd_fds, take_upgrade_fds, upgrade, ListenAddr};
+
 /// Helper trait for convert listener types to tokio types.
 /// This is to delay the conversion and make it happen in server thread(s).
 /// Otherwise it could panic.
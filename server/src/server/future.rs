@@ -45,12 +45,8 @@ impl ServerFuture {
     /// ```
     pub fn handle(&mut self) -> io::Result<ServerHandle> {
         match *self {
-            Self::Init { ref server, .. } => Ok(ServerHandle {
-                tx: server.tx_cmd.clone(),
-            }),
-            Self::Running(ref inner) => Ok(ServerHandle {
-                tx: inner.server.tx_cmd.clone(),
-            }),
+            Self::Init { ref server, .. } => Ok(ServerHandle::new(server)),
+            Self::Running(ref inner) => Ok(ServerHandle::new(&inner.server)),
             Self::Error(_) => match mem::take(self) {
                 Self::Error(e) => Err(e),
                 _ => unreachable!(),
@@ -106,6 +102,9 @@ impl ServerFuture {
 pub struct ServerFutureInner {
     pub(crate) server: Server,
     pub(crate) signals: Option<SignalFuture>,
+    // pending result of handing listeners over to new process.
+    #[cfg(unix)]
+    upgrading: Option<tokio::sync::oneshot::Receiver<io::Result<()>>>,
 }
 
 impl Default for ServerFuture {
@@ -120,17 +119,50 @@ impl ServerFutureInner {
         Self {
             server,
             signals: enable_signal.then(signals::start),
+            #[cfg(unix)]
+            upgrading: None,
         }
     }
 
     #[inline(never)]
     fn poll_cmd(&mut self, cx: &mut Context<'_>) -> Poll<Command> {
+        loop {
+            // server would stop after successful upgrade or keep running otherwise.
+            #[cfg(unix)]
+            if let Some(rx) = self.upgrading.as_mut() {
+                if let Poll::Ready(res) = Pin::new(rx).poll(cx) {
+                    self.upgrading = None;
+                    match res {
+                        Ok(Ok(_)) => return Poll::Ready(Command::GracefulStop),
+                        Ok(Err(e)) => tracing::error!("Server upgrade failed: {e}"),
+                        Err(_) => tracing::error!("Server upgrade failed: upgrade thread exited unexpectedly"),
+                    }
+                }
+            }
+
+            match ready!(self._poll_cmd(cx)) {
+                #[cfg(unix)]
+                Command::Upgrade => match self.upgrading {
+                    Some(_) => tracing::warn!("Server upgrade is already in progress"),
+                    None => match self.server.upgrade() {
+                        Ok(rx) => self.upgrading = Some(rx),
+                        Err(e) => tracing::error!("Server upgrade failed: {e}"),
+                    },
+                },
+                cmd => return Poll::Ready(cmd),
+            }
+        }
+    }
+
+    fn _poll_cmd(&mut self, cx: &mut Context<'_>) -> Poll<Command> {
         if let Some(signals) = self.signals.as_mut() {
             if let Poll::Ready(sig) = Pin::new(signals).poll(cx) {
                 tracing::info!("Signal {:?} received.", sig);
                 let cmd = match sig {
                     Signal::Int | Signal::Quit => Command::ForceStop,
                     Signal::Term => Command::GracefulStop,
+                    #[cfg(unix)]
+                    Signal::Usr2 => Command::Upgrade,
                     // Remove signal listening and keep Server running when
                     // terminal closed which xitca-server process belong.
                     Signal::Hup => {
@@ -157,6 +189,8 @@ impl ServerFutureInner {
             Command::GracefulStop => {
                 self.server.stop(true);
             }
+            #[cfg(unix)]
+            Command::Upgrade => unreachable!("upgrade command is handled in poll_cmd"),
         }
     }
 }
@@ -1,13 +1,38 @@
-use tokio::sync::mpsc::UnboundedSender;
+use std::sync::Arc;
 
-use super::Command;
+use tokio::sync::{mpsc::UnboundedSender, watch};
+
+use crate::worker::Counter;
+
+use super::{Command, Server};
 
 #[derive(Clone)]
 pub struct ServerHandle {
     pub(super) tx: UnboundedSender<Command>,
+    paused: Arc<watch::Sender<bool>>,
+    counters: Vec<Arc<Counter>>,
+}
+
+/// Connection statistic of a server worker thread.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct WorkerStats {
+    /// Number of connections currently being handled by worker.
+    pub active_connections: usize,
+    /// Total number of connections accepted by worker.
+    pub accepted: u64,
+    /// Total number of errors occurred when accepting connection.
+    pub accept_errors: u64,
 }
 
 impl ServerHandle {
+    pub(super) fn new(server: &Server) -> Self {
+        Self {
+            tx: server.tx_cmd.clone(),
+            paused: server.paused.clone(),
+            counters: server.counters.clone(),
+        }
+    }
+
     /// Stop xitca-server with graceful flag.
     pub fn stop(&self, graceful: bool) {
         let cmd = if graceful {
@@ -18,4 +43,44 @@ impl ServerHandle {
 
         let _ = self.tx.send(cmd);
     }
+
+    /// Pause accepting new connections.
+    ///
+    /// Listeners are not closed and connections incoming after this call are queued in their backlog
+    /// until [ServerHandle::resume] is called. Connections already accepted are not affected.
+    pub fn pause(&self) {
+        self.paused.send_replace(true);
+    }
+
+    /// Resume accepting new connections after [ServerHandle::pause].
+    pub fn resume(&self) {
+        self.paused.send_replace(false);
+    }
+
+    /// Connection statistics of server workers. One [WorkerStats] for each worker thread.
+    pub fn stats(&self) -> Vec<WorkerStats> {
+        self.counters.iter().map(|counter| counter.stats()).collect()
+    }
+
+    /// Upgrade xitca-server to a new process without dropping connections.
+    ///
+    /// Current binary is executed again with the same arguments and listening sockets are handed
+    /// to it. Listeners added by [Builder::bind](crate::Builder::bind) and
+    /// [Builder::bind_unix](crate::Builder::bind_unix) in new process use the sockets with the
+    /// same name and address instead of binding again and listeners added by
+    /// [Builder::listen_systemd](crate::Builder::listen_systemd) use the sockets with the same name.
+    /// After new process is built, current server stops accepting and gracefully shuts down within
+    /// shutdown timeout.
+    ///
+    /// When new process fails to start in shutdown timeout it's killed and current server keeps
+    /// running. Upgrade can also be triggered by `SIGUSR2` signal.
+    ///
+    /// Upgrade is refused for server with listeners that can not be handed over, namely the ones
+    /// added by [Builder::listen](crate::Builder::listen),
+    /// [Builder::listen_unix](crate::Builder::listen_unix), [Builder::listen_fd](crate::Builder::listen_fd)
+    /// and the Udp listeners of http/3.
+    #[cfg(unix)]
+    pub fn upgrade(&self) {
+        let _ = self.tx.send(Command::Upgrade);
+    }
 }
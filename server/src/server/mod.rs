@@ -4,7 +4,7 @@ mod service;
 
 pub use self::{
     future::{ServerFuture, ServerFutureInner},
-    handle::ServerHandle,
+    handle::{ServerHandle, WorkerStats},
 };
 
 pub(crate) use self::service::{BuildServiceFn, Factory, _BuildService};
@@ -20,7 +20,10 @@ use std::{
 
 use tokio::{
     runtime::Runtime,
-    sync::mpsc::{UnboundedReceiver, UnboundedSender},
+    sync::{
+        mpsc::{UnboundedReceiver, UnboundedSender},
+        watch,
+    },
 };
 
 use crate::{builder::Builder, worker};
@@ -29,8 +32,19 @@ pub struct Server {
     is_graceful_shutdown: Arc<AtomicBool>,
     tx_cmd: UnboundedSender<Command>,
     rx_cmd: UnboundedReceiver<Command>,
+    // flag for pausing accept loops of all workers.
+    paused: Arc<watch::Sender<bool>>,
+    counters: Vec<Arc<worker::Counter>>,
     rt: Option<Runtime>,
     worker_join_handles: Vec<thread::JoinHandle<()>>,
+    // duplicated listener sockets for handing over to new process on upgrade.
+    #[cfg(unix)]
+    listen_fds: Vec<(String, std::os::unix::io::OwnedFd)>,
+    // names of listeners that can not be handed over to new process on upgrade.
+    #[cfg(unix)]
+    fixed_listeners: Vec<String>,
+    #[cfg(unix)]
+    shutdown_timeout: std::time::Duration,
 }
 
 impl Server {
@@ -62,6 +76,9 @@ impl Server {
 
         let is_graceful_shutdown = Arc::new(AtomicBool::new(false));
 
+        let (_paused, paused) = watch::channel(false);
+        let ctx = worker::Context::new(Default::default(), paused);
+
         let on_start_fut = on_worker_start();
 
         let fut = async {
@@ -72,7 +89,7 @@ impl Server {
 
             for (name, factory) in factories.iter() {
                 let (h, s) = factory
-                    ._build(name, &listeners)
+                    ._build(name, &listeners, &ctx)
                     .await
                     .map_err(|_| io::Error::from(io::ErrorKind::Other))?;
                 handles.extend(h);
@@ -99,6 +116,8 @@ impl Server {
             factories,
             shutdown_timeout,
             on_worker_start,
+            #[cfg(unix)]
+            fixed_listeners,
             ..
         } = builder;
 
@@ -126,10 +145,40 @@ impl Server {
         let listeners = thread::scope(|s| s.spawn(|| rt.block_on(fut)).join())
             .map_err(|e| io::Error::new(io::ErrorKind::Other, format!("{e:?}")))??;
 
+        #[cfg(unix)]
+        let listen_fds = {
+            use std::os::unix::io::AsFd;
+
+            use xitca_io::net::Listener;
+
+            let mut fds = Vec::with_capacity(listeners.len());
+            for (name, listener) in listeners.iter() {
+                let fd = match **listener {
+                    Listener::Tcp(ref l) => l.as_fd(),
+                    Listener::Unix(ref l) => l.as_fd(),
+                    #[cfg(feature = "http3")]
+                    Listener::Udp(_) => continue,
+                };
+                fds.push((name.clone(), fd.try_clone_to_owned()?));
+            }
+            fds
+        };
+
         let is_graceful_shutdown = Arc::new(AtomicBool::new(false));
 
         let is_graceful_shutdown2 = is_graceful_shutdown.clone();
 
+        let (paused, paused_rx) = watch::channel(false);
+
+        let counters = (0..worker_threads)
+            .map(|_| Arc::new(worker::Counter::default()))
+            .collect::<Vec<_>>();
+
+        let ctxs = counters
+            .iter()
+            .map(|counter| worker::Context::new(counter.clone(), paused_rx.clone()))
+            .collect::<Vec<_>>();
+
         let worker_handles = thread::Builder::new()
             .name(String::from("xitca-server-worker-shared-scope"))
             .spawn(move || {
@@ -139,7 +188,7 @@ impl Server {
                     let mut handles = Vec::with_capacity(worker_threads);
 
                     let spawner = |scope| {
-                        for idx in 0..worker_threads {
+                        for (idx, ctx) in ctxs.iter().enumerate() {
                             let thread = thread::Builder::new().name(format!("xitca-server-worker-{idx}"));
 
                             let task = || {
@@ -152,7 +201,7 @@ impl Server {
                                     let mut services = Vec::new();
 
                                     for (name, factory) in factories.iter() {
-                                        let (h, s) = factory._build(name, &listeners).await?;
+                                        let (h, s) = factory._build(name, &listeners, ctx).await?;
                                         handles.extend(h);
                                         services.push(s);
                                     }
@@ -209,14 +258,54 @@ impl Server {
             is_graceful_shutdown,
             tx_cmd,
             rx_cmd,
+            paused: Arc::new(paused),
+            counters,
             rt: Some(rt),
             worker_join_handles: vec![worker_handles],
+            #[cfg(unix)]
+            listen_fds,
+            #[cfg(unix)]
+            fixed_listeners,
+            #[cfg(unix)]
+            shutdown_timeout,
         })
     }
 
+    // hand listeners to a new process of current binary on a dedicated thread. returned receiver
+    // resolves when the new process is ready to serve or failed to start.
+    #[cfg(unix)]
+    pub(crate) fn upgrade(&self) -> io::Result<tokio::sync::oneshot::Receiver<io::Result<()>>> {
+        if let Some(name) = self.fixed_listeners.first() {
+            return Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                format!("listener {name} can not be handed over to new process"),
+            ));
+        }
+
+        let listen_fds = self
+            .listen_fds
+            .iter()
+            .map(|(name, fd)| Ok((name.clone(), fd.try_clone()?)))
+            .collect::<io::Result<Vec<_>>>()?;
+        let timeout = self.shutdown_timeout;
+
+        let (tx, rx) = tokio::sync::oneshot::channel();
+
+        thread::Builder::new()
+            .name(String::from("xitca-server-upgrade"))
+            .spawn(move || {
+                let _ = tx.send(crate::net::upgrade(&listen_fds, timeout));
+            })?;
+
+        Ok(rx)
+    }
+
     pub(crate) fn stop(&mut self, graceful: bool) {
         self.is_graceful_shutdown.store(graceful, Ordering::SeqCst);
 
+        #[cfg(unix)]
+        self.listen_fds.clear();
+
         self.rt.take().unwrap().shutdown_background();
 
         mem::take(&mut self.worker_join_handles).into_iter().for_each(|handle| {
@@ -228,4 +317,6 @@ impl Server {
 enum Command {
     GracefulStop,
     ForceStop,
+    #[cfg(unix)]
+    Upgrade,
 }
@@ -31,6 +31,7 @@ pub(crate) trait _BuildService: Send + Sync {
         &'s self,
         name: &'f str,
         listeners: &'f [(String, Arc<Listener>)],
+        ctx: &'f worker::Context,
     ) -> LocalBoxFuture<'f, BuildServiceSyncOpt>
     where
         's: 'f;
@@ -45,6 +46,7 @@ where
         &'s self,
         name: &'f str,
         listeners: &'f [(String, Arc<Listener>)],
+        ctx: &'f worker::Context,
     ) -> LocalBoxFuture<'f, BuildServiceSyncOpt>
     where
         's: 'f,
@@ -56,7 +58,7 @@ where
             let handles = listeners
                 .iter()
                 .filter(|(n, _)| n == name)
-                .map(|(_, listener)| worker::start(listener, &service))
+                .map(|(_, listener)| worker::start(listener, &service, ctx))
                 .collect::<Vec<_>>();
 
             Ok((handles, service as _))
@@ -12,6 +12,9 @@ pub(crate) enum Signal {
     Term,
     /// SIGQUIT
     Quit,
+    /// SIGUSR2
+    #[cfg(unix)]
+    Usr2,
 }
 
 pub(crate) type SignalFuture = Pin<Box<dyn Future<Output = Signal> + Send>>;
@@ -28,6 +31,7 @@ pub(crate) fn start() -> SignalFuture {
             (unix::SignalKind::hangup(), Signal::Hup),
             (unix::SignalKind::terminate(), Signal::Term),
             (unix::SignalKind::quit(), Signal::Quit),
+            (unix::SignalKind::user_defined2(), Signal::Usr2),
         ]
         .iter()
         .filter_map(|(kind, sig)| {
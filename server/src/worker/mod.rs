@@ -2,24 +2,86 @@ mod shutdown;
 
 use std::{
     any::Any,
+    future::{poll_fn, Future},
     io,
+    pin::pin,
     rc::Rc,
-    sync::{atomic::AtomicBool, Arc},
+    sync::{
+        atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+        Arc,
+    },
+    task::Poll,
     thread,
     time::Duration,
 };
 
-use tokio::{task::JoinHandle, time::sleep};
+use tokio::{sync::watch, task::JoinHandle, time::sleep};
 use tracing::{error, info};
 use xitca_io::net::{Listener, Stream};
 use xitca_service::{ready::ReadyService, Service};
 
+use crate::server::WorkerStats;
+
 use self::shutdown::ShutdownHandle;
 
 // erase Rc<S: ReadyService<_>> type and only use it for counting the reference counter of Rc.
 pub(crate) type ServiceAny = Rc<dyn Any>;
 
-pub(crate) fn start<S, Req>(listener: &Arc<Listener>, service: &S) -> JoinHandle<()>
+// context of worker shared by all accept loops of it.
+#[derive(Clone)]
+pub(crate) struct Context {
+    counter: Arc<Counter>,
+    paused: watch::Receiver<bool>,
+}
+
+impl Context {
+    pub(crate) fn new(counter: Arc<Counter>, paused: watch::Receiver<bool>) -> Self {
+        Self { counter, paused }
+    }
+}
+
+// connection counter of worker.
+#[derive(Default)]
+pub(crate) struct Counter {
+    active: AtomicUsize,
+    accepted: AtomicU64,
+    accept_errors: AtomicU64,
+}
+
+impl Counter {
+    pub(crate) fn stats(&self) -> WorkerStats {
+        WorkerStats {
+            active_connections: self.active.load(Ordering::Relaxed),
+            accepted: self.accepted.load(Ordering::Relaxed),
+            accept_errors: self.accept_errors.load(Ordering::Relaxed),
+        }
+    }
+}
+
+// decrease active connection count when connection task finished or dropped.
+struct ActiveGuard(Arc<Counter>);
+
+impl ActiveGuard {
+    fn new(counter: &Arc<Counter>) -> Self {
+        counter.accepted.fetch_add(1, Ordering::Relaxed);
+        counter.active.fetch_add(1, Ordering::Relaxed);
+        Self(counter.clone())
+    }
+}
+
+impl Drop for ActiveGuard {
+    fn drop(&mut self) {
+        self.0.active.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+enum Accept {
+    Stream(io::Result<Stream>),
+    Paused,
+    Closed,
+}
+
+pub(crate) fn start<S, Req>(listener: &Arc<Listener>, service: &S, ctx: &Context) -> JoinHandle<()>
 where
     S: ReadyService + Service<Req> + Clone + 'static,
     S::Ready: 'static,
@@ -27,17 +89,53 @@ where
 {
     let listener = listener.clone();
     let service = service.clone();
+    let Context { counter, mut paused } = ctx.clone();
 
     tokio::task::spawn_local(async move {
         loop {
+            // wait for resume when accepting is paused. listener is kept open and connections are
+            // queued in it's backlog.
+            while *paused.borrow_and_update() {
+                if paused.changed().await.is_err() {
+                    return;
+                }
+            }
+
             let ready = service.ready().await;
 
-            match listener.accept().await {
+            let accept = {
+                let mut accept = pin!(listener.accept());
+                let mut changed = pin!(paused.changed());
+                // pause is checked first so connections incoming after it are not accepted.
+                poll_fn(|cx| {
+                    match changed.as_mut().poll(cx) {
+                        Poll::Ready(Ok(_)) => return Poll::Ready(Accept::Paused),
+                        Poll::Ready(Err(_)) => return Poll::Ready(Accept::Closed),
+                        Poll::Pending => {}
+                    }
+                    accept.as_mut().poll(cx).map(Accept::Stream)
+                })
+                .await
+            };
+
+            let res = match accept {
+                Accept::Stream(res) => res,
+                Accept::Paused => continue,
+                Accept::Closed => return,
+            };
+
+            if res.is_err() {
+                counter.accept_errors.fetch_add(1, Ordering::Relaxed);
+            }
+
+            match res {
                 Ok(stream) => {
                     let service = service.clone();
+                    let guard = ActiveGuard::new(&counter);
                     tokio::task::spawn_local(async move {
                         let _ = service.call(From::from(stream)).await;
                         drop(ready);
+                        drop(guard);
                     });
                 }
                 Err(ref e) if connection_error(e) => continue,
@@ -10,7 +10,7 @@ use core::{
 use futures_core::stream::Stream;
 use xitca_http::util::service::{
     context::{Context, ContextBuilder},
-    router::GenericRouter,
+    router::{FeatureOr, Features, GenericRouter, HostFeature, IntoRoutes, NestFeature, UrlsFeature},
 };
 
 use crate::{
@@ -35,7 +35,7 @@ pub struct App<CF = (), R = ()> {
     router: R,
 }
 
-type Router<C, B, SF> = GenericRouter<WebObjectConstructor<C, B>, SF>;
+type Router<C, B, SF, F = Features> = GenericRouter<WebObjectConstructor<C, B>, SF, F>;
 
 impl App {
     pub fn new<B, SF>() -> App<impl Fn() -> Ready<Result<(), Infallible>>, Router<(), B, SF>> {
@@ -66,6 +66,17 @@ impl App {
         Self::with_async_state(move || ready(Ok(state.clone())))
     }
 
+    /// Construct a scope of routes that can be nested into App with [App::nest].
+    ///
+    /// State type of scope is inferred from the App it's nested into. A scope can not be finished
+    /// as an App on its own.
+    pub fn scope<C, B, SF>() -> App<(), Router<C, B, SF>> {
+        App {
+            ctx_factory: (),
+            router: GenericRouter::with_custom_object(),
+        }
+    }
+
     #[doc(hidden)]
     /// Construct App with async closure which it's output would be used as state.
     pub fn with_async_state<CF, Fut, E, C, B, SF>(ctx_factory: CF) -> App<CF, Router<C, B, SF>>
@@ -80,14 +91,130 @@ impl App {
     }
 }
 
-impl<CF, C, B, SF> App<CF, Router<C, B, SF>> {
-    pub fn at<F>(mut self, path: &'static str, factory: F) -> App<CF, Router<C, B, SF>>
+impl<CF, C, B, SF, N, U, H> App<CF, Router<C, B, SF, Features<N, U, H>>> {
+    pub fn at<F>(mut self, path: &'static str, factory: F) -> Self
     where
         WebObjectConstructor<C, B>: ObjectConstructor<F, Object = SF>,
     {
         self.router = self.router.insert(path, factory);
         self
     }
+
+    /// Insert a route with a name. URL path of the route can be generated from the name with
+    /// [UrlsRef](crate::handler::urls::UrlsRef) extractor when App is enabled with [App::with_urls].
+    ///
+    /// # Examples:
+    /// ```rust
+    /// # use xitca_web::{handler::{handler_service, urls::UrlsRef}, route::get, request::WebRequest, App};
+    /// async fn index(urls: UrlsRef<'_>) -> String {
+    ///     urls.url_for("user", [("id", "996")]).unwrap()
+    /// }
+    ///
+    /// # async fn user(_: &WebRequest<'_>) {}
+    /// # fn app() {
+    /// App::new()
+    ///     .at("/", get(handler_service(index)))
+    ///     .at_named("user", "/users/:id", get(handler_service(user)))
+    ///     .with_urls();
+    /// # }
+    /// ```
+    pub fn at_named<F>(mut self, name: &'static str, path: &'static str, factory: F) -> Self
+    where
+        WebObjectConstructor<C, B>: ObjectConstructor<F, Object = SF>,
+    {
+        self.router = self.router.insert_named(name, path, factory);
+        self
+    }
+
+    /// Make named routes of App available to [UrlsRef](crate::handler::urls::UrlsRef) extractor.
+    ///
+    /// It's ignored for scope nested into App with [App::nest].
+    #[allow(clippy::type_complexity)]
+    pub fn with_urls(self) -> App<CF, Router<C, B, SF, Features<N, UrlsFeature, H>>> {
+        App {
+            ctx_factory: self.ctx_factory,
+            router: self.router.with_urls(),
+        }
+    }
+
+    /// Nest routes of given scope under path prefix.
+    ///
+    /// Params of prefix are merged with params of nested routes and middleware enclosing the scope is
+    /// applied to every route of it. [PathRef](crate::handler::path::PathRef) extracts the path with
+    /// prefix stripped for nested routes.
+    ///
+    /// # Examples:
+    /// ```rust
+    /// # use xitca_web::{handler::{handler_service, path::PathRef}, route::get, request::WebRequest, App};
+    /// async fn user(PathRef(path): PathRef<'_>, req: &WebRequest<'_>) -> String {
+    ///     // path is the "/users/:id" part of request path.
+    ///     let params = req.req().body().params();
+    ///     let version = params.get("version").unwrap();
+    ///     let id = params.get("id").unwrap();
+    ///     format!("{path}: user {id} of api {version}")
+    /// }
+    ///
+    /// # fn app() {
+    /// let api = App::scope().at("/users/:id", get(handler_service(user)));
+    ///
+    /// App::new().nest("/api/:version", api);
+    /// # }
+    /// ```
+    #[allow(clippy::type_complexity)]
+    pub fn nest<R>(
+        self,
+        prefix: &'static str,
+        scope: App<(), R>,
+    ) -> App<CF, Router<C, B, SF, Features<NestFeature, U, <H as FeatureOr<R::Host>>::Output>>>
+    where
+        R: IntoRoutes,
+        H: FeatureOr<R::Host>,
+        WebObjectConstructor<C, B>: ObjectConstructor<R::Route, Object = SF>,
+    {
+        App {
+            ctx_factory: self.ctx_factory,
+            router: self.router.nest(prefix, scope.router),
+        }
+    }
+
+    /// Serve routes of given scope on hosts matching given pattern.
+    ///
+    /// Routes of matched host are tried before other routes of App and captured host params are merged
+    /// with params of routes. Pattern can be an exact host, a wildcard subdomain like `*.example.com`
+    /// or a captured subdomain like `{tenant}.example.com`.
+    ///
+    /// # Examples:
+    /// ```rust
+    /// # use xitca_web::{handler::handler_service, route::get, request::WebRequest, App};
+    /// async fn user(req: &WebRequest<'_>) -> String {
+    ///     let params = req.req().body().params();
+    ///     let tenant = params.get("tenant").unwrap();
+    ///     let id = params.get("id").unwrap();
+    ///     format!("user {id} of tenant {tenant}")
+    /// }
+    ///
+    /// # fn app() {
+    /// let tenant = App::scope().at("/users/:id", get(handler_service(user)));
+    ///
+    /// App::new().host("{tenant}.example.com", tenant);
+    /// # }
+    /// ```
+    #[allow(clippy::type_complexity)]
+    pub fn host<R>(
+        self,
+        host: &'static str,
+        scope: App<(), R>,
+    ) -> App<CF, Router<C, B, SF, Features<<N as FeatureOr<R::Nest>>::Output, U, HostFeature>>>
+    where
+        R: IntoRoutes,
+        N: FeatureOr<R::Nest>,
+        WebObjectConstructor<C, B>: ObjectConstructor<R::Route, Object = SF>,
+    {
+        App {
+            ctx_factory: self.ctx_factory,
+            router: self.router.host(host, scope.router),
+        }
+    }
 }
 
 impl<CF, R> App<CF, R>
@@ -7,6 +7,7 @@ pub mod request;
 pub mod state;
 pub mod string;
 pub mod uri;
+pub mod urls;
 pub mod vec;
 
 #[cfg(feature = "params")]
@@ -6,6 +6,10 @@ use crate::{
     request::WebRequest,
 };
 
+/// Extract the path of request.
+///
+/// For routes nested with [App::nest](crate::App::nest) the prefix of path matched by nesting is
+/// stripped. Full path can be obtained from [UriRef](crate::handler::uri::UriRef).
 #[derive(Debug)]
 pub struct PathRef<'a>(pub &'a str);
 
@@ -27,6 +31,7 @@ where
 
     #[inline]
     fn from_request(req: &'a WebRequest<'r, C, B>) -> Self::Future {
-        async { Ok(PathRef(req.req().uri().path())) }
+        let path = req.req().body().nested_prefix().strip(req.req().uri().path());
+        async move { Ok(PathRef(path)) }
     }
 }
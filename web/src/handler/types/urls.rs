@@ -0,0 +1,41 @@
+use std::{future::Future, ops::Deref};
+
+use xitca_http::util::service::router::Urls;
+
+use crate::{
+    body::BodyStream,
+    handler::{error::ExtractError, FromRequest},
+    request::WebRequest,
+};
+
+/// Extract named routes of App for generating URL path. See [App::at_named](crate::App::at_named).
+///
+/// Extraction fails with [ExtractError::ExtensionNotFound] when App has no named route or is not
+/// enabled with [App::with_urls](crate::App::with_urls).
+#[derive(Debug)]
+pub struct UrlsRef<'a>(pub &'a Urls);
+
+impl Deref for UrlsRef<'_> {
+    type Target = Urls;
+
+    fn deref(&self) -> &Self::Target {
+        self.0
+    }
+}
+
+impl<'a, 'r, C, B> FromRequest<'a, WebRequest<'r, C, B>> for UrlsRef<'a>
+where
+    B: BodyStream,
+{
+    type Type<'b> = UrlsRef<'b>;
+    type Error = ExtractError<B::Error>;
+    type Future = impl Future<Output = Result<Self, Self::Error>> where WebRequest<'r, C, B>: 'a;
+
+    #[inline]
+    fn from_request(req: &'a WebRequest<'r, C, B>) -> Self::Future {
+        async move {
+            let urls = req.req().body().urls().ok_or(ExtractError::ExtensionNotFound)?;
+            Ok(UrlsRef(urls))
+        }
+    }
+}